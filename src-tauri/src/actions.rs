@@ -1,12 +1,14 @@
 use crate::audio_feedback::{play_recording_start_sound, play_recording_stop_sound};
-use crate::managers::audio::AudioRecordingManager;
-use crate::managers::history::HistoryManager;
+use crate::managers::audio::{check_speech_gate, pad_short_samples, AudioRecordingManager};
+use crate::managers::queue::TranscriptionQueue;
 use crate::managers::transcription::TranscriptionManager;
-use crate::overlay::{show_recording_overlay, show_transcribing_overlay};
+use crate::overlay::{
+    show_nothing_heard_overlay, show_recording_overlay, show_transcribing_overlay,
+};
 use crate::settings::get_settings;
 use crate::tray::{change_tray_icon, TrayIconState};
 use crate::utils;
-use log::{debug, error};
+use log::debug;
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -33,6 +35,7 @@ impl ShortcutAction for TranscribeAction {
         show_recording_overlay(app);
 
         let rm = app.state::<Arc<AudioRecordingManager>>();
+        let tm = app.state::<Arc<TranscriptionManager>>();
 
         // Get the microphone mode to determine audio feedback timing
         let settings = get_settings(app);
@@ -45,6 +48,10 @@ impl ShortcutAction for TranscribeAction {
             play_recording_start_sound(app);
             let recording_started = rm.try_start_recording(&binding_id);
             debug!("Recording started: {}", recording_started);
+            if recording_started {
+                tm.begin_stream();
+                tm.preload_model();
+            }
         } else {
             // On-demand mode: Start recording first, then play audio feedback
             // This allows the microphone to be activated before playing the sound
@@ -52,6 +59,8 @@ impl ShortcutAction for TranscribeAction {
             let recording_start_time = Instant::now();
             if rm.try_start_recording(&binding_id) {
                 debug!("Recording started in {:?}", recording_start_time.elapsed());
+                tm.begin_stream();
+                tm.preload_model();
                 // Small delay to ensure microphone stream is active
                 let app_clone = app.clone();
                 std::thread::spawn(move || {
@@ -76,8 +85,11 @@ impl ShortcutAction for TranscribeAction {
 
         let ah = app.clone();
         let rm = Arc::clone(&app.state::<Arc<AudioRecordingManager>>());
-        let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
-        let hm = Arc::clone(&app.state::<Arc<HistoryManager>>());
+        let queue = Arc::clone(&app.state::<Arc<TranscriptionQueue>>());
+
+        // Detach the streaming session now so the next recording can start
+        // its own while this one waits in the queue
+        let dictation = app.state::<Arc<TranscriptionManager>>().take_stream();
 
         change_tray_icon(app, TrayIconState::Transcribing);
         show_transcribing_overlay(app);
@@ -88,76 +100,38 @@ impl ShortcutAction for TranscribeAction {
         let binding_id = binding_id.to_string(); // Clone binding_id for the async task
 
         tauri::async_runtime::spawn(async move {
-            let binding_id = binding_id.clone(); // Clone for the inner async task
-            debug!(
-                "Starting async transcription task for binding: {}",
-                binding_id
-            );
-
             let stop_recording_time = Instant::now();
-            if let Some(samples) = rm.stop_recording(&binding_id) {
+            if let Some(recording) = rm.stop_recording(&binding_id) {
                 debug!(
                     "Recording stopped and samples retrieved in {:?}, sample count: {}",
                     stop_recording_time.elapsed(),
-                    samples.len()
+                    recording.samples.len()
                 );
 
-                let transcription_time = Instant::now();
-                let samples_clone = samples.clone(); // Clone for history saving
-                match tm.transcribe(samples) {
-                    Ok(transcription) => {
-                        debug!(
-                            "Transcription completed in {:?}: '{}'",
-                            transcription_time.elapsed(),
-                            transcription
-                        );
-                        if !transcription.is_empty() {
-                            // Save to history
-                            let hm_clone = Arc::clone(&hm);
-                            let transcription_for_history = transcription.clone();
-                            tauri::async_runtime::spawn(async move {
-                                if let Err(e) = hm_clone
-                                    .save_transcription(samples_clone, transcription_for_history)
-                                    .await
-                                {
-                                    error!("Failed to save transcription to history: {}", e);
-                                }
-                            });
-                            let transcription_clone = transcription.clone();
-                            let ah_clone = ah.clone();
-                            let paste_time = Instant::now();
-                            ah.run_on_main_thread(move || {
-                                match utils::paste(transcription_clone, ah_clone.clone()) {
-                                    Ok(()) => debug!(
-                                        "Text pasted successfully in {:?}",
-                                        paste_time.elapsed()
-                                    ),
-                                    Err(e) => eprintln!("Failed to paste transcription: {}", e),
-                                }
-                                // Hide the overlay after transcription is complete
-                                utils::hide_recording_overlay(&ah_clone);
-                                change_tray_icon(&ah_clone, TrayIconState::Idle);
-                            })
-                            .unwrap_or_else(|e| {
-                                eprintln!("Failed to run paste on main thread: {:?}", e);
-                                utils::hide_recording_overlay(&ah);
-                                change_tray_icon(&ah, TrayIconState::Idle);
-                            });
-                        } else {
-                            utils::hide_recording_overlay(&ah);
-                            change_tray_icon(&ah, TrayIconState::Idle);
-                        }
-                    }
-                    Err(err) => {
-                        debug!("Global Shortcut Transcription error: {}", err);
-                        utils::hide_recording_overlay(&ah);
+                let gate = get_settings(&ah).speech_gate;
+                if let Err(reason) = check_speech_gate(&recording, &gate) {
+                    debug!(
+                        "Skipping transcription ({:?}): {:.2}s of speech, ratio {:.2}, rms {:.4}",
+                        reason,
+                        recording.speech_seconds(),
+                        recording.speech_ratio(),
+                        recording.rms()
+                    );
+                    dictation.discard();
+                    show_nothing_heard_overlay(&ah);
+                    if queue.depth() == 0 {
                         change_tray_icon(&ah, TrayIconState::Idle);
                     }
+                    return;
                 }
+
+                queue.enqueue(pad_short_samples(recording.samples), dictation);
             } else {
                 debug!("No samples retrieved from recording stop");
-                utils::hide_recording_overlay(&ah);
-                change_tray_icon(&ah, TrayIconState::Idle);
+                if queue.depth() == 0 {
+                    utils::hide_recording_overlay(&ah);
+                    change_tray_icon(&ah, TrayIconState::Idle);
+                }
             }
         });
 
@@ -0,0 +1,134 @@
+use std::ops::Range;
+
+use crate::audio_toolkit::constants;
+
+/// Analysis frame used to look for silence (30 ms at 16 kHz, same as the VAD).
+const FRAME_SAMPLES: usize = (constants::WHISPER_SAMPLE_RATE as usize * 30) / 1000;
+/// Frames quieter than this RMS level (~-40 dBFS) count as a silence gap.
+const SILENCE_RMS: f32 = 0.01;
+/// Audio repeated at both sides of a cut that had to be made inside speech.
+const OVERLAP_SAMPLES: usize = constants::WHISPER_SAMPLE_RATE as usize;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioChunk {
+    pub range: Range<usize>,
+    /// True when the start of this chunk repeats the end of the previous one,
+    /// so the transcripts have to be de-duplicated when stitched together.
+    pub overlaps_previous: bool,
+}
+
+/// Splits a 16 kHz buffer into windows of at most `max_samples`.
+///
+/// Each window is cut at the quietest frame in its second half. If that frame
+/// is not actually silent the cut lands inside speech, so the next window
+/// starts a little earlier and overlaps the previous one.
+pub fn split_at_silence(samples: &[f32], max_samples: usize) -> Vec<AudioChunk> {
+    let max_samples = max_samples.max(2 * OVERLAP_SAMPLES);
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut overlaps_previous = false;
+
+    while samples.len() - start > max_samples {
+        let window_end = start + max_samples;
+        let search_from = start + max_samples / 2;
+
+        let quietest = (search_from..window_end - FRAME_SAMPLES)
+            .step_by(FRAME_SAMPLES)
+            .map(|frame_start| {
+                (
+                    frame_start,
+                    rms(&samples[frame_start..frame_start + FRAME_SAMPLES]),
+                )
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        match quietest {
+            Some((frame_start, level)) if level <= SILENCE_RMS => {
+                let cut = frame_start + FRAME_SAMPLES / 2;
+                chunks.push(AudioChunk {
+                    range: start..cut,
+                    overlaps_previous,
+                });
+                start = cut;
+                overlaps_previous = false;
+            }
+            _ => {
+                chunks.push(AudioChunk {
+                    range: start..window_end,
+                    overlaps_previous,
+                });
+                start = window_end - OVERLAP_SAMPLES;
+                overlaps_previous = true;
+            }
+        }
+    }
+
+    chunks.push(AudioChunk {
+        range: start..samples.len(),
+        overlaps_previous,
+    });
+    chunks
+}
+
+fn rms(frame: &[f32]) -> f32 {
+    if frame.is_empty() {
+        return 0.0;
+    }
+    (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: usize = constants::WHISPER_SAMPLE_RATE as usize;
+
+    fn tone(len: usize) -> Vec<f32> {
+        (0..len).map(|i| (i as f32 * 0.05).sin() * 0.5).collect()
+    }
+
+    #[test]
+    fn short_buffer_is_a_single_chunk() {
+        let samples = tone(5 * SECOND);
+        let chunks = split_at_silence(&samples, 28 * SECOND);
+
+        assert_eq!(
+            chunks,
+            vec![AudioChunk {
+                range: 0..samples.len(),
+                overlaps_previous: false
+            }]
+        );
+    }
+
+    #[test]
+    fn cuts_inside_silence_gap_without_overlap() {
+        let mut samples = tone(20 * SECOND);
+        samples.extend(vec![0.0; SECOND / 2]);
+        samples.extend(tone(20 * SECOND));
+
+        let chunks = split_at_silence(&samples, 28 * SECOND);
+
+        assert_eq!(chunks.len(), 2);
+        let cut = chunks[0].range.end;
+        assert!(cut > 20 * SECOND && cut < 20 * SECOND + SECOND / 2);
+        assert_eq!(chunks[1].range.start, cut);
+        assert!(!chunks[1].overlaps_previous);
+    }
+
+    #[test]
+    fn overlaps_when_no_silence_is_found() {
+        let samples = tone(60 * SECOND);
+        let chunks = split_at_silence(&samples, 28 * SECOND);
+
+        assert!(chunks.len() >= 3);
+        for pair in chunks.windows(2) {
+            assert!(pair[1].overlaps_previous);
+            assert_eq!(pair[1].range.start, pair[0].range.end - OVERLAP_SAMPLES);
+        }
+        for chunk in &chunks {
+            assert!(chunk.range.len() <= 28 * SECOND);
+        }
+        assert_eq!(chunks.last().unwrap().range.end, samples.len());
+    }
+}
@@ -0,0 +1,185 @@
+use anyhow::{anyhow, Context, Result};
+use log::debug;
+use std::fs::File;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::Duration;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::audio_toolkit::{audio::FrameResampler, constants};
+
+const RESAMPLER_FRAME: Duration = Duration::from_millis(30);
+
+/// Decodes an audio file (WAV, FLAC, MP3 or OGG Vorbis) into mono samples at
+/// the rate the transcription engines expect.
+pub fn decode_audio_file(path: &Path) -> Result<Vec<f32>> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(extension);
+    }
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            stream,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .with_context(|| format!("Unsupported audio format: {}", path.display()))?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| anyhow!("No audio track in {}", path.display()))?;
+    let track_id = track.id;
+    let in_hz = track
+        .codec_params
+        .sample_rate
+        .ok_or_else(|| anyhow!("Unknown sample rate in {}", path.display()))?;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .with_context(|| format!("Unsupported audio codec: {}", path.display()))?;
+
+    let mut resampler = FrameResampler::new(
+        in_hz as usize,
+        constants::WHISPER_SAMPLE_RATE as usize,
+        RESAMPLER_FRAME,
+    );
+    let mut output = Vec::new();
+    let mut input_samples = 0;
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e.into()),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // A corrupt packet only costs a few milliseconds of audio
+            Err(SymphoniaError::DecodeError(e)) => {
+                debug!("Skipping undecodable packet: {}", e);
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let spec = *decoded.spec();
+        let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        buffer.copy_interleaved_ref(decoded);
+
+        let mono = downmix(buffer.samples(), spec.channels.count());
+        input_samples += mono.len();
+        resampler.push(&mono, |frame| output.extend_from_slice(frame));
+    }
+    resampler.finish(|frame| output.extend_from_slice(frame));
+
+    // The resampler pads its last chunk and frame with silence
+    let expected = input_samples as u64 * constants::WHISPER_SAMPLE_RATE as u64 / in_hz as u64;
+    output.truncate(expected as usize);
+
+    debug!(
+        "Decoded {} ({} Hz) into {} samples",
+        path.display(),
+        in_hz,
+        output.len()
+    );
+    Ok(output)
+}
+
+/// Averages interleaved frames of `channels` samples into a single channel.
+fn downmix(interleaved: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return interleaved.to_vec();
+    }
+    interleaved
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hound::{SampleFormat, WavSpec, WavWriter};
+
+    fn write_wav(path: &Path, sample_rate: u32, channels: u16, frames: &[Vec<i16>]) {
+        let spec = WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        let mut writer = WavWriter::create(path, spec).unwrap();
+        for frame in frames {
+            for &sample in frame {
+                writer.write_sample(sample).unwrap();
+            }
+        }
+        writer.finalize().unwrap();
+    }
+
+    #[test]
+    fn downmix_averages_channels() {
+        assert_eq!(downmix(&[0.5, -0.5, 1.0, 0.0], 2), vec![0.0, 0.5]);
+        assert_eq!(downmix(&[0.25, 0.75], 1), vec![0.25, 0.75]);
+    }
+
+    #[test]
+    fn decode_keeps_16khz_mono_as_is() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memo.wav");
+        let frames: Vec<Vec<i16>> = (0..16000).map(|i| vec![(i % 100) as i16 * 100]).collect();
+        write_wav(&path, 16000, 1, &frames);
+
+        let samples = decode_audio_file(&path).unwrap();
+        assert_eq!(samples.len(), 16000);
+        assert!((samples[42] - 4200.0 / 32768.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn decode_downmixes_and_resamples_stereo() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memo.wav");
+        // Two seconds of a 440 Hz tone on the left channel only
+        let frames: Vec<Vec<i16>> = (0..88200)
+            .map(|i| {
+                let t = i as f32 / 44100.0;
+                let left = (t * 440.0 * std::f32::consts::TAU).sin() * 16000.0;
+                vec![left as i16, 0]
+            })
+            .collect();
+        write_wav(&path, 44100, 2, &frames);
+
+        let samples = decode_audio_file(&path).unwrap();
+        assert_eq!(samples.len(), 32000);
+        // Half the amplitude of the left channel once averaged with silence
+        let peak = samples[8000..24000]
+            .iter()
+            .fold(0.0f32, |peak, s| peak.max(s.abs()));
+        assert!((peak - 0.244).abs() < 0.02, "peak {}", peak);
+    }
+
+    #[test]
+    fn decode_rejects_unknown_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, "not audio").unwrap();
+        assert!(decode_audio_file(&path).is_err());
+        assert!(decode_audio_file(&dir.path().join("missing.wav")).is_err());
+    }
+}
@@ -1,12 +1,16 @@
 // Re-export all audio components
+mod chunker;
+mod decoder;
 mod device;
 mod recorder;
 mod resampler;
 mod utils;
 mod visualizer;
 
+pub use chunker::{split_at_silence, AudioChunk};
+pub use decoder::decode_audio_file;
 pub use device::{list_input_devices, list_output_devices, CpalDeviceInfo};
-pub use recorder::AudioRecorder;
+pub use recorder::{AudioRecorder, Recording};
 pub use resampler::FrameResampler;
 pub use utils::save_wav_file;
 pub use visualizer::AudioVisualiser;
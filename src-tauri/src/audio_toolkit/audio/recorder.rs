@@ -18,16 +18,56 @@ use crate::audio_toolkit::{
 
 enum Cmd {
     Start,
-    Stop(mpsc::Sender<Vec<f32>>),
+    Stop(mpsc::Sender<Recording>),
     Shutdown,
 }
 
+/// Audio captured between `start` and `stop`.
+#[derive(Debug, Default)]
+pub struct Recording {
+    /// 16 kHz samples the VAD classified as speech, or all of them when the
+    /// recorder has no VAD.
+    pub samples: Vec<f32>,
+    /// Length of the whole recording in samples, speech or not.
+    pub total_samples: usize,
+}
+
+impl Recording {
+    pub fn speech_seconds(&self) -> f32 {
+        self.samples.len() as f32 / constants::WHISPER_SAMPLE_RATE as f32
+    }
+
+    /// Share of the recording the VAD classified as speech.
+    pub fn speech_ratio(&self) -> f32 {
+        if self.total_samples == 0 {
+            return 0.0;
+        }
+        self.samples.len() as f32 / self.total_samples as f32
+    }
+
+    /// Root mean square level of the speech samples.
+    pub fn rms(&self) -> f32 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        let sum: f32 = self.samples.iter().map(|s| s * s).sum();
+        (sum / self.samples.len() as f32).sqrt()
+    }
+}
+
+/// Minimum amount of speech (1 s at 16 kHz) a closed VAD segment needs before it
+/// is published. Shorter segments are merged into the next one.
+const MIN_SEGMENT_SAMPLES: usize = constants::WHISPER_SAMPLE_RATE as usize;
+
+type SampleCallback = Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>;
+
 pub struct AudioRecorder {
     device: Option<Device>,
     cmd_tx: Option<mpsc::Sender<Cmd>>,
     worker_handle: Option<std::thread::JoinHandle<()>>,
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
-    level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    level_cb: Option<SampleCallback>,
+    segment_cb: Option<SampleCallback>,
 }
 
 impl AudioRecorder {
@@ -38,6 +78,7 @@ impl AudioRecorder {
             worker_handle: None,
             vad: None,
             level_cb: None,
+            segment_cb: None,
         })
     }
 
@@ -54,6 +95,17 @@ impl AudioRecorder {
         self
     }
 
+    /// Called with each VAD-delimited speech segment as soon as it closes while
+    /// recording. Segments are contiguous, in order, and together form a prefix
+    /// of the buffer later returned by `stop`.
+    pub fn with_segment_callback<F>(mut self, cb: F) -> Self
+    where
+        F: Fn(Vec<f32>) + Send + Sync + 'static,
+    {
+        self.segment_cb = Some(Arc::new(cb));
+        self
+    }
+
     pub fn open(&mut self, device: Option<Device>) -> Result<(), Box<dyn std::error::Error>> {
         if self.worker_handle.is_some() {
             return Ok(()); // already open
@@ -74,6 +126,7 @@ impl AudioRecorder {
         let vad = self.vad.clone();
         // Move the optional level callback into the worker thread
         let level_cb = self.level_cb.clone();
+        let segment_cb = self.segment_cb.clone();
 
         let worker = std::thread::spawn(move || {
             let config = AudioRecorder::get_preferred_config(&thread_device)
@@ -117,7 +170,7 @@ impl AudioRecorder {
             stream.play().expect("failed to start stream");
 
             // keep the stream alive while we process samples
-            run_consumer(sample_rate, vad, sample_rx, cmd_rx, level_cb);
+            run_consumer(sample_rate, vad, sample_rx, cmd_rx, level_cb, segment_cb);
             // stream is dropped here, after run_consumer returns
         });
 
@@ -135,7 +188,7 @@ impl AudioRecorder {
         Ok(())
     }
 
-    pub fn stop(&self) -> Result<Vec<f32>, Box<dyn std::error::Error>> {
+    pub fn stop(&self) -> Result<Recording, Box<dyn std::error::Error>> {
         let (resp_tx, resp_rx) = mpsc::channel();
         if let Some(tx) = &self.cmd_tx {
             tx.send(Cmd::Stop(resp_tx))?;
@@ -222,12 +275,48 @@ impl AudioRecorder {
     }
 }
 
+/// Tracks where the current speech segment starts in the processed buffer so
+/// that closed segments can be handed out while recording continues.
+#[derive(Default)]
+struct SegmentTracker {
+    start: usize,
+    in_speech: bool,
+}
+
+impl SegmentTracker {
+    fn reset(&mut self) {
+        self.start = 0;
+        self.in_speech = false;
+    }
+
+    fn observe(&mut self, is_speech: bool, buf: &[f32], cb: &Option<SampleCallback>) {
+        if is_speech {
+            self.in_speech = true;
+            return;
+        }
+
+        if !self.in_speech {
+            return;
+        }
+        self.in_speech = false;
+
+        // Speech -> silence transition: publish everything since the last cut
+        if buf.len() - self.start >= MIN_SEGMENT_SAMPLES {
+            if let Some(cb) = cb {
+                cb(buf[self.start..].to_vec());
+            }
+            self.start = buf.len();
+        }
+    }
+}
+
 fn run_consumer(
     in_sample_rate: u32,
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
     sample_rx: mpsc::Receiver<Vec<f32>>,
     cmd_rx: mpsc::Receiver<Cmd>,
-    level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    level_cb: Option<SampleCallback>,
+    segment_cb: Option<SampleCallback>,
 ) {
     let mut frame_resampler = FrameResampler::new(
         in_sample_rate as usize,
@@ -236,7 +325,9 @@ fn run_consumer(
     );
 
     let mut processed_samples = Vec::<f32>::new();
+    let mut total_samples = 0;
     let mut recording = false;
+    let mut segments = SegmentTracker::default();
 
     // ---------- spectrum visualisation setup ---------------------------- //
     const BUCKETS: usize = 16;
@@ -254,19 +345,23 @@ fn run_consumer(
         recording: bool,
         vad: &Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
         out_buf: &mut Vec<f32>,
-    ) {
+    ) -> bool {
         if !recording {
-            return;
+            return false;
         }
 
         if let Some(vad_arc) = vad {
             let mut det = vad_arc.lock().unwrap();
             match det.push_frame(samples).unwrap_or(VadFrame::Speech(samples)) {
-                VadFrame::Speech(buf) => out_buf.extend_from_slice(buf),
-                VadFrame::Noise => {}
+                VadFrame::Speech(buf) => {
+                    out_buf.extend_from_slice(buf);
+                    true
+                }
+                VadFrame::Noise => false,
             }
         } else {
             out_buf.extend_from_slice(samples);
+            true
         }
     }
 
@@ -280,7 +375,11 @@ fn run_consumer(
 
         // ---------- existing pipeline ------------------------------------ //
         frame_resampler.push(&raw, &mut |frame: &[f32]| {
-            handle_frame(frame, recording, &vad, &mut processed_samples)
+            let is_speech = handle_frame(frame, recording, &vad, &mut processed_samples);
+            if recording {
+                total_samples += frame.len();
+                segments.observe(is_speech, &processed_samples, &segment_cb);
+            }
         });
 
         // non-blocking check for a command
@@ -288,6 +387,8 @@ fn run_consumer(
             match cmd {
                 Cmd::Start => {
                     processed_samples.clear();
+                    total_samples = 0;
+                    segments.reset();
                     recording = true;
                     visualizer.reset(); // Reset visualization buffer
                     if let Some(v) = &vad {
@@ -299,10 +400,15 @@ fn run_consumer(
 
                     frame_resampler.finish(&mut |frame: &[f32]| {
                         // we still want to process the last few frames
-                        handle_frame(frame, true, &vad, &mut processed_samples)
+                        handle_frame(frame, true, &vad, &mut processed_samples);
+                        total_samples += frame.len();
                     });
+                    segments.reset();
 
-                    let _ = reply_tx.send(std::mem::take(&mut processed_samples));
+                    let _ = reply_tx.send(Recording {
+                        samples: std::mem::take(&mut processed_samples),
+                        total_samples,
+                    });
                 }
                 Cmd::Shutdown => {
                     recording = false;
@@ -0,0 +1,61 @@
+const ENGLISH: &[&str] = &[
+    "the", "be", "to", "of", "and", "a", "in", "that", "have", "i", "it", "for", "not", "on",
+    "with", "he", "as", "you", "do", "at", "this", "but", "his", "by", "from", "they", "we", "say",
+    "her", "she", "or", "an", "will", "my", "one", "all", "would", "there", "their", "what", "so",
+    "up", "out", "if", "about", "who", "get", "which", "go", "me", "when", "make", "can", "like",
+    "time", "no", "just", "him", "know", "take", "people", "into", "year", "your", "good", "some",
+    "could", "them", "see", "other", "than", "then", "now", "look", "only", "come", "its", "over",
+    "think", "also", "back", "after", "use", "two", "how", "our", "work", "first", "well", "way",
+    "even", "new", "want", "because", "any", "these", "give", "day", "most", "us", "is", "are",
+    "was", "were", "been", "has", "had", "did", "said", "should", "here", "where", "why", "very",
+    "much", "more", "need", "right", "thing", "things", "let", "put", "still", "send", "call",
+    "today", "tomorrow", "please", "thanks", "yes", "okay",
+];
+
+const GERMAN: &[&str] = &[
+    "der", "die", "und", "in", "den", "von", "zu", "das", "mit", "sich", "des", "auf", "für",
+    "ist", "im", "dem", "nicht", "ein", "eine", "als", "auch", "es", "an", "werden", "aus", "er",
+    "hat", "dass", "sie", "nach", "wird", "bei", "einer", "um", "am", "sind", "noch", "wie",
+    "einem", "über", "einen", "so", "zum", "war", "haben", "nur", "oder", "aber", "vor", "zur",
+    "bis", "mehr", "durch", "man", "dann", "soll", "wenn", "was", "wir", "ich", "du", "ihr",
+    "mich", "mir", "dir", "uns", "schon", "jetzt", "hier", "da", "kann", "können", "muss",
+    "müssen", "gibt", "geht", "machen", "heute", "morgen", "gut", "ja", "nein", "bitte", "danke",
+    "alle", "immer", "sehr", "viel", "neue", "zwei", "also", "doch", "mal", "weil", "ob", "sein",
+    "seine", "ihre", "diese", "dieser", "wo", "warum",
+];
+
+const FRENCH: &[&str] = &[
+    "le", "la", "les", "de", "des", "du", "un", "une", "et", "à", "en", "est", "que", "qui",
+    "dans", "pour", "pas", "sur", "au", "aux", "il", "elle", "on", "nous", "vous", "ils", "je",
+    "tu", "ce", "cette", "ces", "se", "ne", "plus", "par", "avec", "son", "sa", "ses", "mais",
+    "ou", "où", "comme", "tout", "tous", "faire", "fait", "être", "avoir", "a", "ont", "sont",
+    "était", "peut", "bien", "aussi", "leur", "y", "si", "deux", "très", "même", "encore", "alors",
+    "donc", "après", "avant", "quand", "moi", "toi", "lui", "mon", "ma", "mes", "ton", "ta",
+    "notre", "votre", "oui", "non", "merci", "demain", "jour", "temps", "chose", "dire", "voir",
+    "aller", "va", "veux", "besoin", "ici", "pourquoi", "comment",
+];
+
+const SPANISH: &[&str] = &[
+    "de", "la", "que", "el", "en", "y", "a", "los", "se", "del", "las", "un", "por", "con", "no",
+    "una", "su", "para", "es", "al", "lo", "como", "más", "pero", "sus", "le", "ya", "o", "fue",
+    "este", "ha", "sí", "porque", "esta", "son", "entre", "está", "cuando", "muy", "sin", "sobre",
+    "ser", "tiene", "también", "me", "hasta", "hay", "donde", "han", "quien", "desde", "todo",
+    "nos", "durante", "todos", "uno", "les", "ni", "contra", "otros", "ese", "eso", "había",
+    "ante", "ellos", "e", "esto", "mí", "antes", "algunos", "qué", "unos", "yo", "otro", "otras",
+    "otra", "él", "tanto", "esa", "estos", "mucho", "nada", "bien", "hacer", "puede", "tengo",
+    "hoy", "mañana", "gracias", "favor", "dos", "tu", "te", "mi", "ahora", "aquí", "siempre",
+];
+
+/// The most frequent everyday words of `language` ("en", "de-AT"), or none
+/// for languages without a list. Fuzzy custom word correction leaves them
+/// alone, since a permissive threshold would otherwise turn "there" into
+/// "Terra".
+pub fn frequent_words(language: &str) -> &'static [&'static str] {
+    match language.split(['-', '_']).next().unwrap_or(language) {
+        "en" => ENGLISH,
+        "de" => GERMAN,
+        "fr" => FRENCH,
+        "es" => SPANISH,
+        _ => &[],
+    }
+}
@@ -0,0 +1,341 @@
+use serde::Serialize;
+use std::ops::Range;
+
+/// Sentences Whisper produces from silence or noise, learned from subtitled
+/// videos. Compared after lowercasing and stripping punctuation.
+const KNOWN_PHRASES: &[&str] = &[
+    "thanks for watching",
+    "thank you for watching",
+    "thank you so much for watching",
+    "thanks for watching and see you next time",
+    "please subscribe",
+    "please subscribe to my channel",
+    "like and subscribe",
+    "dont forget to like and subscribe",
+    "see you in the next video",
+    "subtitles by the amaraorg community",
+    "transcribed by otterai",
+];
+
+/// Bracketed tags Whisper emits for non-speech, e.g. `[BLANK_AUDIO]` or
+/// `[ Music ]`. Compared after lowercasing and keeping only letters.
+const ANNOTATION_TAGS: &[&str] = &[
+    "blankaudio",
+    "nospeech",
+    "silence",
+    "music",
+    "musicplaying",
+    "noise",
+    "backgroundnoise",
+    "inaudible",
+    "applause",
+    "laughter",
+    "laughing",
+    "typing",
+    "clicking",
+    "sound",
+    "soundeffect",
+];
+
+/// Plausible things to say, but also what Whisper makes of near-silence. Only
+/// dropped when they are all that was heard in a very short recording.
+const SHORT_RECORDING_PHRASES: &[&str] = &["you", "thank you", "thank you very much", "bye"];
+const SHORT_RECORDING_SECONDS: f32 = 1.5;
+
+/// Consecutive repeats of the same sentence or phrase beyond these counts are
+/// treated as a decoding loop.
+const MAX_SENTENCE_REPEATS: usize = 2;
+const MAX_PHRASE_REPEATS: usize = 3;
+const MAX_PHRASE_WORDS: usize = 4;
+
+/// Fast speech is around 3 words per second; anything well beyond this rate,
+/// plus some slack for very short recordings, cannot have been spoken.
+const MAX_WORDS_PER_SECOND: f32 = 5.0;
+const WORD_SLACK: f32 = 6.0;
+
+/// Why text was removed from a transcript.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hallucination {
+    KnownPhrase,
+    Repetition,
+    TooLong,
+}
+
+impl Hallucination {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Hallucination::KnownPhrase => "known_phrase",
+            Hallucination::Repetition => "repetition",
+            Hallucination::TooLong => "too_long",
+        }
+    }
+}
+
+/// Removes typical Whisper hallucinations from `text`, which was transcribed
+/// from `speech_seconds` of audio.
+///
+/// Known subtitle phrases and non-speech annotations such as `[BLANK_AUDIO]`
+/// are removed, runaway repetition is collapsed to a single occurrence, and
+/// text too long to have been spoken in the recording is dropped entirely.
+/// Returns the remaining text and, if anything was removed, the reason.
+pub fn filter_hallucinations(text: &str, speech_seconds: f32) -> (String, Option<Hallucination>) {
+    let mut reason = None;
+
+    // Whisper marks non-speech as "[BLANK_AUDIO]", "[Music]" and the like
+    let cleaned = strip_annotations(text);
+    if cleaned.len() != text.len() {
+        reason = Some(Hallucination::KnownPhrase);
+    }
+
+    let sentences = split_sentences(&cleaned);
+    let mut kept: Vec<&str> = Vec::new();
+    for sentence in &sentences {
+        if is_known_phrase(sentence) {
+            reason = Some(Hallucination::KnownPhrase);
+            continue;
+        }
+        let repeats = kept
+            .iter()
+            .rev()
+            .take_while(|k| normalize(k) == normalize(sentence))
+            .count();
+        if repeats >= MAX_SENTENCE_REPEATS {
+            reason = Some(Hallucination::Repetition);
+            continue;
+        }
+        kept.push(sentence);
+    }
+
+    // Loops that end in a single sentence, e.g. "no no no no no no"
+    let mut words: Vec<&str> = kept.iter().flat_map(|s| s.split_whitespace()).collect();
+    if collapse_repeated_phrases(&mut words) {
+        reason = Some(Hallucination::Repetition);
+    }
+
+    let only_phrase =
+        kept.len() == 1 && SHORT_RECORDING_PHRASES.contains(&normalize(kept[0]).as_str());
+    if only_phrase && speech_seconds < SHORT_RECORDING_SECONDS {
+        return (String::new(), Some(Hallucination::KnownPhrase));
+    }
+
+    if words.len() as f32 > speech_seconds * MAX_WORDS_PER_SECOND + WORD_SLACK {
+        return (String::new(), Some(Hallucination::TooLong));
+    }
+
+    match reason {
+        Some(_) => (words.join(" "), reason),
+        None => (text.to_string(), None),
+    }
+}
+
+/// Splits after sentence-ending punctuation, keeping it with the sentence.
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        if matches!(c, '.' | '!' | '?' | '\n') {
+            let end = i + c.len_utf8();
+            // Keep "..." and "?!" together
+            if text[end..].starts_with(['.', '!', '?']) {
+                continue;
+            }
+            sentences.push(text[start..end].trim());
+            start = end;
+        }
+    }
+    sentences.push(text[start..].trim());
+    sentences.retain(|s| !s.is_empty());
+    sentences
+}
+
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Removes bracketed non-speech tags. Other brackets, such as "[sic]" or
+/// "items[0]", are kept unless the text is nothing but bracketed spans.
+fn strip_annotations(text: &str) -> String {
+    let spans = bracket_spans(text);
+    let mut outside = String::new();
+    let mut last = 0;
+    for span in &spans {
+        outside.push_str(&text[last..span.start]);
+        last = span.end;
+    }
+    outside.push_str(&text[last..]);
+    let only_brackets = !spans.is_empty() && outside.trim().is_empty();
+
+    let mut stripped = String::with_capacity(text.len());
+    let mut last = 0;
+    for span in spans {
+        let inner = &text[span.start + 1..span.end - 1];
+        if only_brackets || is_annotation_tag(inner) {
+            stripped.push_str(&text[last..span.start]);
+            last = span.end;
+        }
+    }
+    stripped.push_str(&text[last..]);
+    stripped
+}
+
+/// Byte ranges of the outermost balanced `[...]` spans, brackets included.
+fn bracket_spans(text: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '[' => {
+                if depth == 0 {
+                    start = i;
+                }
+                depth += 1;
+            }
+            ']' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    spans.push(start..i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
+fn is_annotation_tag(tag: &str) -> bool {
+    let letters: String = tag
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .flat_map(|c| c.to_lowercase())
+        .collect();
+    ANNOTATION_TAGS.contains(&letters.as_str())
+}
+
+fn is_known_phrase(sentence: &str) -> bool {
+    // A whole sentence in parentheses, e.g. "(upbeat music)"
+    let is_annotation = sentence.starts_with('(') && sentence.ends_with(')');
+    is_annotation || KNOWN_PHRASES.contains(&normalize(sentence).as_str())
+}
+
+/// Keeps one copy of any phrase of up to `MAX_PHRASE_WORDS` words repeated
+/// back to back more than `MAX_PHRASE_REPEATS` times. Returns whether
+/// anything was removed.
+fn collapse_repeated_phrases(words: &mut Vec<&str>) -> bool {
+    let keys: Vec<String> = words.iter().map(|w| normalize(w)).collect();
+    let mut kept = Vec::with_capacity(words.len());
+
+    let mut i = 0;
+    while i < words.len() {
+        let run = (1..=MAX_PHRASE_WORDS).find_map(|len| {
+            let phrase = keys.get(i..i + len)?;
+            let repeats = (1..)
+                .take_while(|n| keys.get(i + n * len..i + (n + 1) * len) == Some(phrase))
+                .count()
+                + 1;
+            (repeats > MAX_PHRASE_REPEATS).then_some((len, repeats))
+        });
+
+        match run {
+            Some((len, repeats)) => {
+                kept.extend_from_slice(&words[i..i + len]);
+                i += len * repeats;
+            }
+            None => {
+                kept.push(words[i]);
+                i += 1;
+            }
+        }
+    }
+
+    let collapsed = kept.len() < words.len();
+    *words = kept;
+    collapsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_keeps_normal_speech() {
+        let text = "Thank you for the update. I will send the report tomorrow.";
+        assert_eq!(filter_hallucinations(text, 4.0), (text.to_string(), None));
+    }
+
+    #[test]
+    fn test_filter_removes_known_phrases() {
+        assert_eq!(
+            filter_hallucinations("Thanks for watching!", 0.8),
+            (String::new(), Some(Hallucination::KnownPhrase))
+        );
+        assert_eq!(
+            filter_hallucinations("See you at noon. [BLANK_AUDIO] Please subscribe.", 3.0),
+            (
+                "See you at noon.".to_string(),
+                Some(Hallucination::KnownPhrase)
+            )
+        );
+        // A short "thank you" is only dropped when the recording was tiny
+        assert_eq!(
+            filter_hallucinations("Thank you.", 0.5).1,
+            Some(Hallucination::KnownPhrase)
+        );
+        assert_eq!(filter_hallucinations("Thank you.", 2.0).1, None);
+    }
+
+    #[test]
+    fn test_filter_only_strips_annotation_brackets() {
+        assert_eq!(
+            filter_hallucinations("[ Music ] Read items[0] as written [sic].", 4.0),
+            (
+                "Read items[0] as written [sic].".to_string(),
+                Some(Hallucination::KnownPhrase)
+            )
+        );
+        assert_eq!(
+            filter_hallucinations("Read items[0] as written [sic].", 4.0),
+            ("Read items[0] as written [sic].".to_string(), None)
+        );
+        // Nothing but brackets is not speech, whatever the tag
+        assert_eq!(
+            filter_hallucinations("[door slams] [footsteps]", 2.0),
+            (String::new(), Some(Hallucination::KnownPhrase))
+        );
+    }
+
+    #[test]
+    fn test_filter_collapses_repetition() {
+        assert_eq!(
+            filter_hallucinations("Okay. Okay. Okay. Okay. Let's start.", 5.0),
+            (
+                "Okay. Okay. Let's start.".to_string(),
+                Some(Hallucination::Repetition)
+            )
+        );
+        assert_eq!(
+            filter_hallucinations("I think so so so so so so", 4.0),
+            ("I think so".to_string(), Some(Hallucination::Repetition))
+        );
+        assert_eq!(
+            filter_hallucinations("we can we can we can we can go", 4.0),
+            ("we can go".to_string(), Some(Hallucination::Repetition))
+        );
+    }
+
+    #[test]
+    fn test_filter_drops_text_too_long_for_audio() {
+        let text = "This is a very long sentence with far too many words to have been spoken in one second of audio.";
+        assert_eq!(
+            filter_hallucinations(text, 1.0),
+            (String::new(), Some(Hallucination::TooLong))
+        );
+    }
+}
@@ -0,0 +1,120 @@
+/// Very common function words per language. They make up a large share of any
+/// running text, so counting them is enough to tell these languages apart.
+const STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "is", "are", "was", "to", "of", "in", "that", "it", "you", "this",
+            "with", "for", "not", "have", "what",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "ich", "ein", "eine", "zu", "mit", "sie",
+            "es", "auf", "auch", "wir", "sich",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "et", "est", "une", "je", "pas", "que", "des", "du", "pour", "dans",
+            "nous", "vous", "avec", "ce",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "los", "las", "y", "es", "que", "una", "por", "con", "para", "del", "pero",
+            "muy", "está", "yo", "como", "se",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "il", "gli", "e", "è", "che", "di", "una", "non", "per", "con", "sono", "della", "io",
+            "questo", "anche", "ma", "mi",
+        ],
+    ),
+    (
+        "nl",
+        &[
+            "de", "het", "een", "en", "is", "niet", "ik", "dat", "van", "op", "zijn", "met",
+            "voor", "ook", "maar", "wij", "je",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "o", "os", "as", "e", "é", "que", "não", "um", "uma", "para", "com", "do", "da", "eu",
+            "mas", "isso", "muito",
+        ],
+    ),
+    (
+        "pl",
+        &[
+            "i", "w", "nie", "na", "się", "to", "jest", "że", "z", "do", "co", "jak", "ale", "tak",
+            "ja", "mnie", "jestem",
+        ],
+    ),
+];
+
+/// Guesses the language of a transcript from its function words.
+///
+/// Only the languages in `candidates` are considered; an empty slice means
+/// every language with a stopword table. Returns `None` when no candidate
+/// matched a single word, e.g. for very short or unsupported text.
+pub fn detect_language(text: &str, candidates: &[String]) -> Option<String> {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphabetic()).to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+
+    let mut best: Option<(&str, usize)> = None;
+    for (language, stopwords) in STOPWORDS {
+        if !candidates.is_empty() && !candidates.iter().any(|c| c == language) {
+            continue;
+        }
+        let hits = words
+            .iter()
+            .filter(|w| stopwords.contains(&w.as_str()))
+            .count();
+        if hits > 0 && best.is_none_or(|(_, best_hits)| hits > best_hits) {
+            best = Some((language, hits));
+        }
+    }
+
+    best.map(|(language, _)| language.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language_among_all() {
+        assert_eq!(
+            detect_language("The meeting is moved to Friday and that is fine.", &[]),
+            Some("en".to_string())
+        );
+        assert_eq!(
+            detect_language("Ich glaube, das ist nicht die richtige Datei.", &[]),
+            Some("de".to_string())
+        );
+        assert_eq!(detect_language("", &[]), None);
+    }
+
+    #[test]
+    fn test_detect_language_limited_to_candidates() {
+        let candidates = vec!["fr".to_string(), "de".to_string()];
+        assert_eq!(
+            detect_language("Nous avons une réunion avec le client.", &candidates),
+            Some("fr".to_string())
+        );
+        assert_eq!(detect_language("The report is ready.", &candidates), None);
+    }
+}
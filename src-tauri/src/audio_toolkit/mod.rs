@@ -1,10 +1,23 @@
 pub mod audio;
 pub mod constants;
+pub mod frequent_words;
+pub mod hallucination;
+pub mod language;
+pub mod punctuation;
+pub mod replacement;
 pub mod text;
 pub mod vad;
 
 pub use audio::{
-    list_input_devices, list_output_devices, save_wav_file, AudioRecorder, CpalDeviceInfo,
+    decode_audio_file, list_input_devices, list_output_devices, save_wav_file, AudioRecorder,
+    CpalDeviceInfo, Recording,
+};
+pub use hallucination::{filter_hallucinations, Hallucination};
+pub use language::detect_language;
+pub use punctuation::apply_spoken_punctuation;
+pub use replacement::{apply_replacement_rules, CompiledRules, ReplacementRule};
+pub use text::{
+    append_deduplicated, apply_custom_words, build_initial_prompt, correct_custom_words,
+    protected_word_set, WordCorrection,
 };
-pub use text::apply_custom_words;
 pub use vad::{SileroVad, VoiceActivityDetector};
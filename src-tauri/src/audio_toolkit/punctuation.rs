@@ -0,0 +1,351 @@
+use std::collections::HashMap;
+
+/// How a spoken command's output joins the words around it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Spacing {
+    /// Punctuation mark that replaces any the engine put before it, e.g. ","
+    Mark,
+    /// Attaches to the previous word, e.g. a closing quote
+    Close,
+    /// Attaches to the next word, e.g. an opening quote
+    Open,
+    /// Line breaks, with no spaces on either side
+    Break,
+    /// Spaced like an ordinary word
+    Word,
+}
+
+/// Spoken commands of a language, the phrase prefixing a command that is
+/// meant literally, and what each command turns into.
+struct LanguageTable {
+    literal: &'static str,
+    commands: &'static [(&'static str, &'static str)],
+}
+
+const ENGLISH: LanguageTable = LanguageTable {
+    literal: "literal",
+    commands: &[
+        ("comma", ","),
+        ("period", "."),
+        ("full stop", "."),
+        ("question mark", "?"),
+        ("exclamation mark", "!"),
+        ("exclamation point", "!"),
+        ("colon", ":"),
+        ("semicolon", ";"),
+        ("new line", "\n"),
+        ("new paragraph", "\n\n"),
+        ("open quote", "\""),
+        ("close quote", "\""),
+        ("open paren", "("),
+        ("close paren", ")"),
+    ],
+};
+
+const GERMAN: LanguageTable = LanguageTable {
+    literal: "wörtlich",
+    commands: &[
+        ("komma", ","),
+        ("punkt", "."),
+        ("fragezeichen", "?"),
+        ("ausrufezeichen", "!"),
+        ("doppelpunkt", ":"),
+        ("semikolon", ";"),
+        ("neue zeile", "\n"),
+        ("neuer absatz", "\n\n"),
+        ("anführungszeichen auf", "\""),
+        ("anführungszeichen zu", "\""),
+        ("klammer auf", "("),
+        ("klammer zu", ")"),
+    ],
+};
+
+const FRENCH: LanguageTable = LanguageTable {
+    literal: "littéralement",
+    commands: &[
+        ("virgule", ","),
+        ("point", "."),
+        ("point d'interrogation", "?"),
+        ("point d'exclamation", "!"),
+        ("deux points", ":"),
+        ("deux-points", ":"),
+        ("point-virgule", ";"),
+        ("à la ligne", "\n"),
+        ("nouveau paragraphe", "\n\n"),
+        ("ouvrez les guillemets", "\""),
+        ("fermez les guillemets", "\""),
+        ("ouvrez la parenthèse", "("),
+        ("fermez la parenthèse", ")"),
+    ],
+};
+
+const SPANISH: LanguageTable = LanguageTable {
+    literal: "literal",
+    commands: &[
+        ("coma", ","),
+        ("punto", "."),
+        ("signo de interrogación", "?"),
+        ("signo de exclamación", "!"),
+        ("dos puntos", ":"),
+        ("punto y coma", ";"),
+        ("nueva línea", "\n"),
+        ("nuevo párrafo", "\n\n"),
+        ("abrir comillas", "\""),
+        ("cerrar comillas", "\""),
+        ("abrir paréntesis", "("),
+        ("cerrar paréntesis", ")"),
+    ],
+};
+
+fn language_table(language: &str) -> Option<&'static LanguageTable> {
+    match language.split(['-', '_']).next().unwrap_or(language) {
+        "en" => Some(&ENGLISH),
+        "de" => Some(&GERMAN),
+        "fr" => Some(&FRENCH),
+        "es" => Some(&SPANISH),
+        _ => None,
+    }
+}
+
+/// Opening quotes and parentheses share their text with closing ones, so the
+/// phrase decides which side they attach to.
+fn spacing(phrase: &str, output: &str) -> Spacing {
+    const OPENING_WORDS: &[&str] = &["open", "auf", "ouvrez", "abrir"];
+    match output {
+        "," | "." | "?" | "!" | ":" | ";" => Spacing::Mark,
+        "(" => Spacing::Open,
+        ")" => Spacing::Close,
+        "\"" if phrase
+            .split_whitespace()
+            .any(|w| OPENING_WORDS.contains(&w)) =>
+        {
+            Spacing::Open
+        }
+        "\"" => Spacing::Close,
+        _ if output.trim().is_empty() => Spacing::Break,
+        _ => Spacing::Word,
+    }
+}
+
+struct Command {
+    words: Vec<String>,
+    output: String,
+    spacing: Spacing,
+}
+
+/// Turns spoken punctuation and formatting commands such as "comma" or
+/// "new paragraph" into the characters they stand for, using the commands of
+/// `language` plus `custom` phrases, which take precedence. Saying "literal"
+/// (or its translation) before a command keeps the words themselves.
+pub fn apply_spoken_punctuation(
+    text: &str,
+    language: &str,
+    custom: &HashMap<String, String>,
+) -> String {
+    let table = language_table(language);
+    let mut commands: Vec<Command> = custom
+        .iter()
+        .map(|(phrase, output)| (phrase.as_str(), output.as_str()))
+        .chain(table.iter().flat_map(|t| t.commands.iter().copied()))
+        .map(|(phrase, output)| Command {
+            words: phrase.split_whitespace().map(str::to_lowercase).collect(),
+            output: output.to_string(),
+            spacing: spacing(phrase, output),
+        })
+        .filter(|c| !c.words.is_empty())
+        .collect();
+    if commands.is_empty() {
+        return text.to_string();
+    }
+    // Longest phrases first, so "punto y coma" wins over "punto"; the stable
+    // sort keeps custom phrases ahead of built-in ones of the same length
+    commands.sort_by_key(|c| std::cmp::Reverse(c.words.len()));
+    let literal = table.map(|t| t.literal);
+
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let keys: Vec<String> = tokens.iter().map(|t| normalize_token(t)).collect();
+    let find_command = |at: usize| {
+        commands
+            .iter()
+            .find(|c| keys.get(at..at + c.words.len()) == Some(&c.words[..]))
+    };
+
+    let mut output = Output::default();
+    let mut changed = false;
+    let mut i = 0;
+    while i < tokens.len() {
+        if literal == Some(keys[i].as_str()) {
+            if let Some(command) = find_command(i + 1) {
+                for token in &tokens[i + 1..i + 1 + command.words.len()] {
+                    output.push_word(token);
+                }
+                i += 1 + command.words.len();
+                changed = true;
+                continue;
+            }
+        }
+        match find_command(i) {
+            Some(command) => {
+                output.push_command(command);
+                i += command.words.len();
+                changed = true;
+            }
+            None => {
+                output.push_word(tokens[i]);
+                i += 1;
+            }
+        }
+    }
+
+    if changed {
+        output.text
+    } else {
+        text.to_string()
+    }
+}
+
+/// Lowercased, without the punctuation engines attach to words.
+fn normalize_token(token: &str) -> String {
+    token
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+#[derive(Default)]
+struct Output {
+    text: String,
+    space_before_next: bool,
+    capitalize_next: bool,
+}
+
+impl Output {
+    fn push_word(&mut self, word: &str) {
+        if self.space_before_next {
+            self.text.push(' ');
+        }
+        if self.capitalize_next {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                self.text.extend(first.to_uppercase());
+                self.text.push_str(chars.as_str());
+            }
+        } else {
+            self.text.push_str(word);
+        }
+        self.space_before_next = true;
+        self.capitalize_next = false;
+    }
+
+    fn push_command(&mut self, command: &Command) {
+        match command.spacing {
+            Spacing::Mark => {
+                // "Hello, comma" should not end up as "Hello,,"
+                let trimmed = self
+                    .text
+                    .trim_end_matches([',', '.', '?', '!', ':', ';'])
+                    .len();
+                self.text.truncate(trimmed);
+                self.text.push_str(&command.output);
+                self.space_before_next = true;
+                self.capitalize_next = matches!(command.output.as_str(), "." | "?" | "!");
+            }
+            Spacing::Close => {
+                self.text.push_str(&command.output);
+                self.space_before_next = true;
+            }
+            Spacing::Open => {
+                if self.space_before_next {
+                    self.text.push(' ');
+                }
+                self.text.push_str(&command.output);
+                self.space_before_next = false;
+            }
+            Spacing::Break => {
+                let trimmed = self.text.trim_end_matches(' ').len();
+                self.text.truncate(trimmed);
+                self.text.push_str(&command.output);
+                self.space_before_next = false;
+                self.capitalize_next = true;
+            }
+            Spacing::Word => self.push_word(&command.output),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn english(text: &str) -> String {
+        apply_spoken_punctuation(text, "en", &HashMap::new())
+    }
+
+    #[test]
+    fn converts_spoken_punctuation() {
+        assert_eq!(
+            english("Hi Anna comma thanks for the notes period see you Friday question mark"),
+            "Hi Anna, thanks for the notes. See you Friday?"
+        );
+        // Punctuation the engine already added is replaced, not doubled
+        assert_eq!(english("Hello, comma, world."), "Hello, world.");
+        assert_eq!(
+            english("No spoken commands here."),
+            "No spoken commands here."
+        );
+    }
+
+    #[test]
+    fn formats_line_breaks_and_quotes() {
+        assert_eq!(
+            english("Dear team comma new paragraph the build is green period"),
+            "Dear team,\n\nThe build is green."
+        );
+        assert_eq!(
+            english("She said open quote ship it close quote and left"),
+            "She said \"ship it\" and left"
+        );
+        assert_eq!(
+            english("call it open paren maybe close paren later"),
+            "call it (maybe) later"
+        );
+    }
+
+    #[test]
+    fn literal_keeps_the_words() {
+        assert_eq!(
+            english("Add a literal comma here comma please"),
+            "Add a comma here, please"
+        );
+        assert_eq!(
+            english("the literal new line character"),
+            "the new line character"
+        );
+    }
+
+    #[test]
+    fn uses_language_tables_and_custom_phrases() {
+        assert_eq!(
+            apply_spoken_punctuation("uno punto y coma dos punto", "es", &HashMap::new()),
+            "uno; dos."
+        );
+        assert_eq!(
+            apply_spoken_punctuation(
+                "Hallo Komma wie geht's Fragezeichen",
+                "de-DE",
+                &HashMap::new()
+            ),
+            "Hallo, wie geht's?"
+        );
+        // No table for Japanese, so nothing changes
+        assert_eq!(
+            apply_spoken_punctuation("comma", "ja", &HashMap::new()),
+            "comma"
+        );
+
+        let custom = HashMap::from([("smiley face".to_string(), ":)".to_string())]);
+        assert_eq!(
+            apply_spoken_punctuation("see you soon smiley face", "en", &custom),
+            "see you soon :)"
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use anyhow::{Context, Result};
+use log::warn;
+use regex::{NoExpand, Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplacementKind {
+    /// The pattern is plain text
+    #[default]
+    Literal,
+    /// The pattern is a regular expression and the replacement may refer to
+    /// its groups as `$1` or `${name}`
+    Regex,
+}
+
+/// Explicit replacement such as "jason" to "JSON", for corrections fuzzy
+/// matching of custom words can't make without false positives.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReplacementRule {
+    pub pattern: String,
+    pub replacement: String,
+    #[serde(default)]
+    pub kind: ReplacementKind,
+    /// Only match whole words, so "sequel" leaves "sequels" alone
+    #[serde(default = "default_true")]
+    pub whole_word: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl ReplacementRule {
+    /// Builds the regex matching this rule, failing for an empty pattern or
+    /// an invalid regular expression.
+    pub fn compile(&self) -> Result<Regex> {
+        if self.pattern.is_empty() {
+            anyhow::bail!("Replacement pattern is empty");
+        }
+        let pattern = match self.kind {
+            ReplacementKind::Literal => regex::escape(&self.pattern),
+            ReplacementKind::Regex => self.pattern.clone(),
+        };
+        // Half boundaries, so patterns starting or ending in a symbol ("C++")
+        // still count as whole words
+        let pattern = if self.whole_word {
+            format!(r"\b{{start-half}}(?:{})\b{{end-half}}", pattern)
+        } else {
+            pattern
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(!self.case_sensitive)
+            .build()
+            .with_context(|| format!("Invalid replacement pattern '{}'", self.pattern))
+    }
+}
+
+/// The enabled rules, compiled once so a transcript and each of its segments
+/// can be rewritten without building the regexes again.
+pub struct CompiledRules<'a> {
+    rules: Vec<(Regex, &'a ReplacementRule)>,
+}
+
+impl<'a> CompiledRules<'a> {
+    /// Compiles the enabled `rules`. Rules that fail to compile are skipped.
+    pub fn new(rules: &'a [ReplacementRule]) -> Self {
+        let rules = rules
+            .iter()
+            .filter(|r| r.enabled)
+            .filter_map(|rule| match rule.compile() {
+                Ok(regex) => Some((regex, rule)),
+                Err(e) => {
+                    warn!("Skipping replacement rule: {:#}", e);
+                    None
+                }
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Applies the rules to `text` one after the other in the order given, so
+    /// a rule sees the output of the rules before it.
+    pub fn apply(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for (regex, rule) in &self.rules {
+            let replaced = match rule.kind {
+                ReplacementKind::Literal => regex.replace_all(&text, NoExpand(&rule.replacement)),
+                ReplacementKind::Regex => regex.replace_all(&text, rule.replacement.as_str()),
+            };
+            text = replaced.into_owned();
+        }
+        text
+    }
+}
+
+/// Applies the enabled `rules` to `text`, see `CompiledRules`.
+pub fn apply_replacement_rules(text: &str, rules: &[ReplacementRule]) -> String {
+    CompiledRules::new(rules).apply(text)
+}
+
+/// Writes `rules` as CSV with a header row.
+pub fn rules_to_csv(rules: &[ReplacementRule]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for rule in rules {
+        writer.serialize(rule)?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+/// Reads rules from CSV with a header row. Only the `pattern` and
+/// `replacement` columns are required; the others fall back to their
+/// defaults. Every rule must compile. Values are taken as written, spaces
+/// included.
+pub fn rules_from_csv(csv: &str) -> Result<Vec<ReplacementRule>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::Headers)
+        .from_reader(csv.as_bytes());
+
+    let mut rules = Vec::new();
+    for (i, record) in reader.deserialize().enumerate() {
+        // Line 1 is the header
+        let rule: ReplacementRule = record.with_context(|| format!("Invalid row {}", i + 2))?;
+        rule.compile()
+            .with_context(|| format!("Invalid row {}", i + 2))?;
+        rules.push(rule);
+    }
+    Ok(rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn literal(pattern: &str, replacement: &str) -> ReplacementRule {
+        ReplacementRule {
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            kind: ReplacementKind::Literal,
+            whole_word: true,
+            case_sensitive: false,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn literal_rules_respect_word_boundaries_and_case() {
+        let rules = vec![literal("jason", "JSON"), literal("sequel", "SQL")];
+        assert_eq!(
+            apply_replacement_rules("Parse the Jason, then run the sequel query", &rules),
+            "Parse the JSON, then run the SQL query"
+        );
+        assert_eq!(
+            apply_replacement_rules("Jasonville sequels", &rules),
+            "Jasonville sequels"
+        );
+
+        let case_sensitive = ReplacementRule {
+            case_sensitive: true,
+            ..literal("jason", "JSON")
+        };
+        assert_eq!(
+            apply_replacement_rules("Jason sent jason", &[case_sensitive]),
+            "Jason sent JSON"
+        );
+
+        let anywhere = ReplacementRule {
+            whole_word: false,
+            ..literal("colour", "color")
+        };
+        assert_eq!(
+            apply_replacement_rules("watercolours", &[anywhere]),
+            "watercolors"
+        );
+
+        // "$" is not a group reference in literal replacements
+        assert_eq!(
+            apply_replacement_rules("five bucks", &[literal("bucks", "$1")]),
+            "five $1"
+        );
+        assert_eq!(
+            apply_replacement_rules("see plus plus", &[literal("see plus plus", "C++")]),
+            "C++"
+        );
+        assert_eq!(
+            apply_replacement_rules("C++ code", &[literal("C++", "C plus plus")]),
+            "C plus plus code"
+        );
+    }
+
+    #[test]
+    fn regex_rules_apply_in_order() {
+        let rules = vec![
+            ReplacementRule {
+                kind: ReplacementKind::Regex,
+                ..literal(r"(\d+) percent", "$1%")
+            },
+            literal("50%", "half"),
+            ReplacementRule {
+                enabled: false,
+                ..literal("half", "disabled")
+            },
+            ReplacementRule {
+                kind: ReplacementKind::Regex,
+                ..literal("(unclosed", "skipped")
+            },
+        ];
+        assert_eq!(
+            apply_replacement_rules("50 percent done, 20 percent left", &rules),
+            "half done, 20% left"
+        );
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let rules = vec![
+            literal("jason", "JSON"),
+            ReplacementRule {
+                kind: ReplacementKind::Regex,
+                whole_word: false,
+                case_sensitive: true,
+                enabled: false,
+                ..literal(r"(\d+), (\d+)", "$1 to $2")
+            },
+        ];
+        let csv = rules_to_csv(&rules).unwrap();
+        assert!(csv.starts_with("pattern,replacement,kind,whole_word,case_sensitive,enabled\n"));
+        assert_eq!(rules_from_csv(&csv).unwrap(), rules);
+    }
+
+    #[test]
+    fn csv_import_fills_defaults_and_reports_bad_rows() {
+        let rules = rules_from_csv("pattern, replacement\njason,JSON\nsequel,SQL\n").unwrap();
+        assert_eq!(
+            rules,
+            vec![literal("jason", "JSON"), literal("sequel", "SQL")]
+        );
+        let rules = rules_from_csv("pattern,replacement\ne mail,\" email \"\n").unwrap();
+        assert_eq!(rules, vec![literal("e mail", " email ")]);
+
+        let error =
+            rules_from_csv("pattern,replacement,kind\nok,fine,literal\n(,x,regex\n").unwrap_err();
+        assert!(format!("{:#}", error).contains("row 3"), "{:#}", error);
+        assert!(rules_from_csv("pattern,replacement,kind\na,b,glob\n").is_err());
+    }
+}
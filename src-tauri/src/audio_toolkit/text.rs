@@ -1,6 +1,33 @@
+use crate::audio_toolkit::frequent_words::frequent_words;
 use natural::phonetics::soundex;
+use serde::Serialize;
+use std::collections::HashSet;
 use strsim::levenshtein;
 
+/// Longest run of transcribed words compared against a custom word, so that
+/// entries the model split up or merged ("kuber netes", "hardenedhandy") can
+/// still be matched.
+const MAX_NGRAM_WORDS: usize = 4;
+
+/// A custom word or phrase, prepared for matching.
+struct CustomWord<'a> {
+    original: &'a str,
+    /// Lowercase words of the entry
+    words: Vec<String>,
+    /// Lowercase entry without whitespace
+    joined: String,
+}
+
+/// A replacement made by the custom word correction, reported in debug mode
+/// so the threshold can be tuned.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WordCorrection {
+    pub original: String,
+    pub replacement: String,
+    /// 0.0 for an exact match; only scores below the threshold are replaced
+    pub score: f64,
+}
+
 /// Applies custom word corrections to transcribed text using fuzzy matching
 ///
 /// This function corrects words in the input text by finding the best matches
@@ -8,89 +35,342 @@ use strsim::levenshtein;
 /// - Levenshtein distance for string similarity
 /// - Soundex phonetic matching for pronunciation similarity
 ///
+/// Runs of up to four words are compared with whitespace removed, so a custom
+/// phrase like "Kubernetes operator" replaces "kuber netes operator" and
+/// "Hardened Handy" replaces "hardenedhandy".
+///
 /// # Arguments
 /// * `text` - The input text to correct
-/// * `custom_words` - List of custom words to match against
+/// * `custom_words` - List of custom words or phrases to match against
 /// * `threshold` - Maximum similarity score to accept (0.0 = exact match, 1.0 = any match)
 ///
 /// # Returns
 /// The corrected text with custom words applied
 pub fn apply_custom_words(text: &str, custom_words: &[String], threshold: f64) -> String {
+    correct_custom_words(text, custom_words, threshold, &HashSet::new()).0
+}
+
+/// Same as [`apply_custom_words`], but leaves the lowercase
+/// `protected_words` alone (see [`protected_word_set`]) and also returns
+/// every replacement made along with its score.
+pub fn correct_custom_words(
+    text: &str,
+    custom_words: &[String],
+    threshold: f64,
+    protected_words: &HashSet<String>,
+) -> (String, Vec<WordCorrection>) {
     if custom_words.is_empty() {
-        return text.to_string();
+        return (text.to_string(), Vec::new());
     }
 
     // Pre-compute lowercase versions to avoid repeated allocations
-    let custom_words_lower: Vec<String> = custom_words.iter().map(|w| w.to_lowercase()).collect();
+    let custom_words: Vec<CustomWord> = custom_words
+        .iter()
+        .map(|w| CustomWord {
+            original: w.trim(),
+            words: w.split_whitespace().map(|p| p.to_lowercase()).collect(),
+            joined: w.split_whitespace().collect::<String>().to_lowercase(),
+        })
+        .filter(|w| !w.joined.is_empty())
+        .collect();
 
     let words: Vec<&str> = text.split_whitespace().collect();
+    let cleaned_words: Vec<String> = words
+        .iter()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphabetic()).to_lowercase())
+        .collect();
     let mut corrected_words = Vec::new();
+    let mut corrections = Vec::new();
+
+    let mut i = 0;
+    while i < words.len() {
+        let (n, replacement, score) = match best_custom_word(
+            &words[i..],
+            &cleaned_words[i..],
+            &custom_words,
+            threshold,
+            protected_words,
+        ) {
+            Some(found) => found,
+            None => {
+                corrected_words.push(words[i].to_string());
+                i += 1;
+                continue;
+            }
+        };
+
+        // Preserve the original case pattern as much as possible
+        let matched: String = words[i..i + n].concat();
+        let corrected = preserve_case_pattern(
+            matched.trim_matches(|c: char| !c.is_alphabetic()),
+            replacement,
+        );
+
+        let original = words[i..i + n].join(" ");
+        let original = original.trim_matches(|c: char| !c.is_alphabetic());
+        if original != corrected {
+            corrections.push(WordCorrection {
+                original: original.to_string(),
+                replacement: corrected.clone(),
+                score,
+            });
+        }
+
+        // Preserve punctuation from the original words
+        let (prefix, _) = extract_punctuation(words[i]);
+        let (_, suffix) = extract_punctuation(words[i + n - 1]);
+        corrected_words.push(format!("{}{}{}", prefix, corrected, suffix));
+        i += n;
+    }
 
-    for word in words {
-        let cleaned_word = word
-            .trim_matches(|c: char| !c.is_alphabetic())
-            .to_lowercase();
+    (corrected_words.join(" "), corrections)
+}
 
-        if cleaned_word.is_empty() {
-            corrected_words.push(word.to_string());
+/// Builds the words [`correct_custom_words`] must leave alone: the user's
+/// `protected_words`, plus the most frequent words of `language` when given.
+/// Frequent words that are part of a custom word stay correctable, so a
+/// custom "Go" still fixes the case of "go".
+pub fn protected_word_set(
+    protected_words: &[String],
+    custom_words: &[String],
+    language: Option<&str>,
+) -> HashSet<String> {
+    let custom: HashSet<String> = custom_words
+        .iter()
+        .flat_map(|w| w.split_whitespace())
+        .map(|w| w.to_lowercase())
+        .collect();
+
+    let frequent = language
+        .map(frequent_words)
+        .unwrap_or_default()
+        .iter()
+        .map(|w| w.to_string())
+        .filter(|w| !custom.contains(w));
+
+    protected_words
+        .iter()
+        .map(|w| w.trim().to_lowercase())
+        .filter(|w| !w.is_empty())
+        .chain(frequent)
+        .collect()
+}
+
+/// Finds the custom word best matching a run of words at the start of
+/// `words`. Returns the length of the run, the replacement and its score. On
+/// equal scores the longer run wins. Runs with a protected word are skipped.
+fn best_custom_word<'a>(
+    words: &[&str],
+    cleaned_words: &[String],
+    custom_words: &[CustomWord<'a>],
+    threshold: f64,
+    protected_words: &HashSet<String>,
+) -> Option<(usize, &'a str, f64)> {
+    let mut best_match = None;
+    let mut best_score = f64::MAX;
+
+    for n in (1..=MAX_NGRAM_WORDS.min(words.len())).rev() {
+        let ngram = &cleaned_words[..n];
+        if ngram
+            .iter()
+            .any(|w| w.is_empty() || protected_words.contains(w))
+        {
+            continue;
+        }
+        // Punctuation inside the run, like the end of a sentence, splits it
+        let punctuated_inside = words[..n - 1]
+            .iter()
+            .any(|w| !extract_punctuation(w).1.is_empty())
+            || words[1..n]
+                .iter()
+                .any(|w| !extract_punctuation(w).0.is_empty());
+        if punctuated_inside {
             continue;
         }
 
+        let joined: String = ngram.concat();
         // Skip extremely long words to avoid performance issues
-        if cleaned_word.len() > 50 {
-            corrected_words.push(word.to_string());
+        if joined.len() > 50 {
             continue;
         }
 
-        let mut best_match: Option<&String> = None;
-        let mut best_score = f64::MAX;
-
-        for (i, custom_word_lower) in custom_words_lower.iter().enumerate() {
-            // Skip if lengths are too different (optimization)
-            let len_diff = (cleaned_word.len() as i32 - custom_word_lower.len() as i32).abs();
-            if len_diff > 5 {
+        for custom_word in custom_words {
+            let score = match_score(ngram, &joined, custom_word);
+            // Accept if the score is good enough (configurable threshold)
+            if score >= threshold || score >= best_score {
                 continue;
             }
 
-            // Calculate Levenshtein distance (normalized by length)
-            let levenshtein_dist = levenshtein(&cleaned_word, custom_word_lower);
-            let max_len = cleaned_word.len().max(custom_word_lower.len()) as f64;
-            let levenshtein_score = if max_len > 0.0 {
-                levenshtein_dist as f64 / max_len
-            } else {
-                1.0
-            };
-
-            // Calculate phonetic similarity using Soundex
-            let phonetic_match = soundex(&cleaned_word, custom_word_lower);
-
-            // Combine scores: favor phonetic matches, but also consider string similarity
-            let combined_score = if phonetic_match {
-                levenshtein_score * 0.3 // Give significant boost to phonetic matches
-            } else {
-                levenshtein_score
-            };
-
-            // Accept if the score is good enough (configurable threshold)
-            if combined_score < threshold && combined_score < best_score {
-                best_match = Some(&custom_words[i]);
-                best_score = combined_score;
+            // Every word of a run has to count towards the match, otherwise
+            // "the kuber netes" would swallow "the"
+            let padded = n > 1
+                && [&ngram[1..], &ngram[..n - 1]]
+                    .iter()
+                    .any(|shorter| match_score(shorter, &shorter.concat(), custom_word) <= score);
+            if !padded {
+                best_match = Some((n, custom_word.original, score));
+                best_score = score;
             }
         }
+    }
 
-        if let Some(replacement) = best_match {
-            // Preserve the original case pattern as much as possible
-            let corrected = preserve_case_pattern(word, replacement);
+    best_match
+}
+
+/// Appends `next` to `previous`, dropping words at the start of `next` that
+/// repeat the end of `previous`.
+///
+/// Used when stitching transcripts of audio chunks that overlap, where both
+/// sides of the overlap were transcribed. Words are compared ignoring case and
+/// punctuation, and at most `max_overlap_words` words are considered.
+pub fn append_deduplicated(previous: &str, next: &str, max_overlap_words: usize) -> String {
+    let normalize = |w: &str| -> String {
+        w.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(|c| c.to_lowercase())
+            .collect()
+    };
+
+    let prev_words: Vec<String> = previous.split_whitespace().map(normalize).collect();
+    let next_words: Vec<&str> = next.split_whitespace().collect();
+    let next_norm: Vec<String> = next_words.iter().map(|w| normalize(w)).collect();
+
+    let max_k = max_overlap_words
+        .min(prev_words.len())
+        .min(next_words.len());
+    let overlap = (1..=max_k)
+        .rev()
+        .find(|&k| prev_words[prev_words.len() - k..] == next_norm[..k])
+        .unwrap_or(0);
+
+    let remainder = next_words[overlap..].join(" ");
+    match (previous.trim().is_empty(), remainder.is_empty()) {
+        (true, _) => remainder,
+        (false, true) => previous.trim().to_string(),
+        (false, false) => format!("{} {}", previous.trim(), remainder),
+    }
+}
 
-            // Preserve punctuation from original word
-            let (prefix, suffix) = extract_punctuation(word);
-            corrected_words.push(format!("{}{}{}", prefix, corrected, suffix));
-        } else {
-            corrected_words.push(word.to_string());
+/// Builds an initial prompt that biases Whisper towards the user's vocabulary.
+///
+/// The prompt lists as many custom words as fit, followed by the optional
+/// context sentence and the tail of the previous dictation. Whisper reads the
+/// prompt as the text spoken right before the audio, so the previous text goes
+/// last and may take up to half of `max_chars`. Words are dropped first once the
+/// limit is reached.
+///
+/// # Returns
+/// `None` when there is nothing to put in the prompt
+pub fn build_initial_prompt(
+    custom_words: &[String],
+    context: &str,
+    previous_text: &str,
+    max_chars: usize,
+) -> Option<String> {
+    let previous = tail_at_word_boundary(previous_text.trim(), max_chars / 2);
+    let context =
+        truncate_at_word_boundary(context.trim(), max_chars.saturating_sub(previous.len() + 1));
+
+    // Reserve room for the separators between the parts
+    let mut budget = max_chars.saturating_sub(context.len() + previous.len() + 2);
+    let mut words: Vec<&str> = Vec::new();
+    for word in custom_words
+        .iter()
+        .map(|w| w.trim())
+        .filter(|w| !w.is_empty())
+    {
+        let cost = word.len() + if words.is_empty() { 1 } else { 2 };
+        if cost > budget {
+            break;
         }
+        budget -= cost;
+        words.push(word);
     }
 
-    corrected_words.join(" ")
+    let vocabulary = if words.is_empty() {
+        String::new()
+    } else {
+        format!("{}.", words.join(", "))
+    };
+
+    let parts: Vec<&str> = [vocabulary.as_str(), context, previous]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect();
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" "))
+    }
+}
+
+/// Keeps the last `max_chars` bytes of `text` without splitting a word.
+fn tail_at_word_boundary(text: &str, max_chars: usize) -> &str {
+    if text.len() <= max_chars {
+        return text;
+    }
+
+    let min_start = text.len() - max_chars;
+    let mut prev_is_whitespace = false;
+    for (idx, c) in text.char_indices() {
+        if idx >= min_start && prev_is_whitespace {
+            return text[idx..].trim_start();
+        }
+        prev_is_whitespace = c.is_whitespace();
+    }
+    ""
+}
+
+/// Cuts `text` to at most `max_chars` bytes without splitting a word.
+fn truncate_at_word_boundary(text: &str, max_chars: usize) -> &str {
+    if text.len() <= max_chars {
+        return text;
+    }
+
+    let mut end = 0;
+    for (idx, c) in text.char_indices() {
+        if idx + c.len_utf8() > max_chars {
+            break;
+        }
+        if c.is_whitespace() {
+            end = idx;
+        }
+    }
+    text[..end].trim_end()
+}
+
+/// Scores how well a run of cleaned words matches a custom word, from 0.0
+/// for an exact match. `joined` is the run without whitespace.
+fn match_score(ngram: &[String], joined: &str, custom_word: &CustomWord) -> f64 {
+    // Skip if lengths are too different (optimization)
+    if joined.len().abs_diff(custom_word.joined.len()) > 5 {
+        return f64::MAX;
+    }
+
+    // Calculate Levenshtein distance (normalized by length)
+    let levenshtein_dist = levenshtein(joined, &custom_word.joined);
+    let max_len = joined.len().max(custom_word.joined.len()) as f64;
+    let levenshtein_score = if max_len > 0.0 {
+        levenshtein_dist as f64 / max_len
+    } else {
+        1.0
+    };
+
+    // Calculate phonetic similarity using Soundex, word by word. Soundex only
+    // looks at the start of a word, so runs of a different length than the
+    // custom word rely on the string similarity alone.
+    let phonetic_match = ngram.len() == custom_word.words.len()
+        && ngram
+            .iter()
+            .zip(&custom_word.words)
+            .all(|(word, custom)| soundex(word, custom));
+
+    // Combine scores: favor phonetic matches, but also consider string similarity
+    if phonetic_match {
+        levenshtein_score * 0.3 // Give significant boost to phonetic matches
+    } else {
+        levenshtein_score
+    }
 }
 
 /// Preserves the case pattern of the original word when applying a replacement
@@ -152,6 +432,84 @@ mod tests {
         assert_eq!(result, "hello world");
     }
 
+    #[test]
+    fn test_apply_custom_words_multi_word_phrases() {
+        let custom_words = vec![
+            "Kubernetes operator".to_string(),
+            "Hardened Handy".to_string(),
+        ];
+        assert_eq!(
+            apply_custom_words("deploy the kubernetes operater today", &custom_words, 0.18),
+            "deploy the Kubernetes operator today"
+        );
+        // Split and merged by the model
+        assert_eq!(
+            apply_custom_words("the kuber netes operator, again", &custom_words, 0.18),
+            "the Kubernetes operator, again"
+        );
+        assert_eq!(
+            apply_custom_words("I use hardenedhandy.", &custom_words, 0.18),
+            "I use Hardened Handy."
+        );
+        assert_eq!(
+            apply_custom_words("HARDENED HANDY rocks", &custom_words, 0.18),
+            "HARDENED HANDY rocks"
+        );
+    }
+
+    #[test]
+    fn test_apply_custom_words_splits_runs_at_punctuation() {
+        let custom_words = vec!["Hardened Handy".to_string(), "Kubernetes".to_string()];
+        assert_eq!(
+            apply_custom_words("it was hardened. Handy though", &custom_words, 0.18),
+            "it was hardened. Handy though"
+        );
+        assert_eq!(
+            apply_custom_words("Kuber netes is up", &custom_words, 0.18),
+            "Kubernetes is up"
+        );
+    }
+
+    #[test]
+    fn test_apply_custom_words_skips_protected_words() {
+        let custom_words = vec!["Terra".to_string(), "Go".to_string()];
+        // Permissive enough to turn "there" into "Terra"
+        let (text, corrections) =
+            correct_custom_words("there is go code", &custom_words, 0.5, &HashSet::new());
+        assert_eq!(text, "Terra is Go code");
+        assert_eq!(
+            corrections,
+            vec![
+                WordCorrection {
+                    original: "there".to_string(),
+                    replacement: "Terra".to_string(),
+                    score: 0.18,
+                },
+                WordCorrection {
+                    original: "go".to_string(),
+                    replacement: "Go".to_string(),
+                    score: 0.0,
+                },
+            ]
+        );
+
+        // "there" and "go" are both frequent, but "go" is also a custom word
+        let protected = protected_word_set(&[], &custom_words, Some("en-US"));
+        assert!(protected.contains("there"));
+        assert!(!protected.contains("go"));
+        let (text, corrections) =
+            correct_custom_words("there is go code", &custom_words, 0.5, &protected);
+        assert_eq!(text, "there is Go code");
+        assert_eq!(corrections.len(), 1);
+
+        let protected = protected_word_set(&[" GO ".to_string()], &custom_words, None);
+        assert_eq!(protected, HashSet::from(["go".to_string()]));
+        assert_eq!(
+            correct_custom_words("there is go code", &custom_words, 0.5, &protected).0,
+            "Terra is go code"
+        );
+    }
+
     #[test]
     fn test_preserve_case_pattern() {
         assert_eq!(preserve_case_pattern("HELLO", "world"), "WORLD");
@@ -166,6 +524,55 @@ mod tests {
         assert_eq!(extract_punctuation("...hello..."), ("...", "..."));
     }
 
+    #[test]
+    fn test_append_deduplicated_removes_overlap() {
+        let result = append_deduplicated("we should ship it on", "Ship it on Friday.", 8);
+        assert_eq!(result, "we should ship it on Friday.");
+    }
+
+    #[test]
+    fn test_append_deduplicated_without_overlap() {
+        assert_eq!(append_deduplicated("hello", "world", 8), "hello world");
+        assert_eq!(append_deduplicated("", "world", 8), "world");
+        assert_eq!(append_deduplicated("hello", "", 8), "hello");
+    }
+
+    #[test]
+    fn test_build_initial_prompt_combines_words_and_context() {
+        let words = vec!["Hardened Handy".to_string(), "Kubernetes".to_string()];
+        let prompt = build_initial_prompt(&words, "Meeting notes for the platform team.", "", 200);
+        assert_eq!(
+            prompt.as_deref(),
+            Some("Hardened Handy, Kubernetes. Meeting notes for the platform team.")
+        );
+    }
+
+    #[test]
+    fn test_build_initial_prompt_respects_limit() {
+        let words: Vec<String> = (0..50).map(|i| format!("word{}", i)).collect();
+        let prompt = build_initial_prompt(&words, "", "", 40).unwrap();
+        assert!(prompt.len() <= 40);
+        assert!(prompt.starts_with("word0, word1"));
+
+        let context = "one two three four five six seven";
+        let prompt = build_initial_prompt(&[], context, "", 15).unwrap();
+        assert_eq!(prompt, "one two three");
+    }
+
+    #[test]
+    fn test_build_initial_prompt_empty() {
+        assert_eq!(build_initial_prompt(&[], "  ", "", 100), None);
+    }
+
+    #[test]
+    fn test_build_initial_prompt_appends_previous_tail() {
+        let words = vec!["Handy".to_string()];
+        let previous = "First we fixed the build. Then we shipped the release";
+        let prompt = build_initial_prompt(&words, "Status update.", previous, 60).unwrap();
+        assert!(prompt.len() <= 60);
+        assert_eq!(prompt, "Handy. Status update. Then we shipped the release");
+    }
+
     #[test]
     fn test_empty_custom_words() {
         let text = "hello world";
@@ -0,0 +1,40 @@
+use crate::managers::batch::{BatchManager, BatchStatus};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::State;
+
+#[derive(Serialize)]
+pub struct BatchStarted {
+    pub batch_id: u64,
+    pub file_count: usize,
+}
+
+#[tauri::command]
+pub fn start_batch_transcription(
+    batch_manager: State<Arc<BatchManager>>,
+    directory: String,
+) -> Result<BatchStarted, String> {
+    let (batch_id, file_count) = batch_manager
+        .start_batch(Path::new(&directory))
+        .map_err(|e| e.to_string())?;
+    Ok(BatchStarted {
+        batch_id,
+        file_count,
+    })
+}
+
+#[tauri::command]
+pub fn pause_batch_transcription(batch_manager: State<Arc<BatchManager>>) {
+    batch_manager.pause();
+}
+
+#[tauri::command]
+pub fn resume_batch_transcription(batch_manager: State<Arc<BatchManager>>) {
+    batch_manager.resume();
+}
+
+#[tauri::command]
+pub fn get_batch_status(batch_manager: State<Arc<BatchManager>>) -> BatchStatus {
+    batch_manager.status()
+}
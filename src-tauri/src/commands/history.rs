@@ -1,4 +1,5 @@
 use crate::managers::history::{HistoryEntry, HistoryManager};
+use crate::managers::transcription::TranscriptSegment;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
@@ -25,6 +26,18 @@ pub async fn toggle_history_entry_saved(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn get_history_segments(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+) -> Result<Vec<TranscriptSegment>, String> {
+    history_manager
+        .get_segments(id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn stream_history_audio(
     _app: AppHandle,
@@ -1,6 +1,8 @@
-use crate::managers::model::{ModelInfo, ModelManager};
+use crate::engines::EngineCapabilities;
+use crate::managers::benchmark::{BenchmarkManager, BenchmarkResults};
+use crate::managers::model::{EngineType, ModelInfo, ModelManager};
 use crate::managers::transcription::TranscriptionManager;
-use crate::settings::{get_settings, write_settings};
+use crate::settings::{get_settings, write_settings, ModelInferenceSettings, ModelPrecision};
 use anyhow::Error;
 use serde::Serialize;
 use std::sync::Arc;
@@ -132,6 +134,64 @@ pub async fn set_active_model(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn get_model_inference_capabilities(
+    model_manager: State<'_, Arc<ModelManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+    model_id: String,
+) -> Result<EngineCapabilities, String> {
+    let model_info = model_manager
+        .get_model_info(&model_id)
+        .ok_or_else(|| format!("Model not found: {}", model_id))?;
+
+    transcription_manager
+        .engine_capabilities(model_info.engine_type)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_model_inference_settings(
+    app_handle: AppHandle,
+    model_manager: State<'_, Arc<ModelManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+    model_id: String,
+    params: ModelInferenceSettings,
+) -> Result<(), String> {
+    let model_info = model_manager
+        .get_model_info(&model_id)
+        .ok_or_else(|| format!("Model not found: {}", model_id))?;
+
+    transcription_manager
+        .engine_capabilities(model_info.engine_type)
+        .and_then(|capabilities| capabilities.validate(&params))
+        .map_err(|e| format!("{}: {}", model_info.name, e))?;
+
+    let mut settings = get_settings(&app_handle);
+    if params == ModelInferenceSettings::default() {
+        settings.model_inference.remove(&model_id);
+    } else {
+        settings.model_inference.insert(model_id, params);
+    }
+    write_settings(&app_handle, settings);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_parakeet_precision(
+    app_handle: AppHandle,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+    precision: ModelPrecision,
+) -> Result<(), String> {
+    let mut settings = get_settings(&app_handle);
+    settings.parakeet_precision = precision;
+    write_settings(&app_handle, settings);
+
+    // The precision is chosen when a model is loaded
+    transcription_manager.evict_engine_models(EngineType::Parakeet);
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn get_current_model(app_handle: AppHandle) -> Result<String, String> {
     let settings = get_settings(&app_handle);
@@ -182,7 +242,28 @@ pub async fn cancel_download(
 }
 
 #[tauri::command]
-pub async fn get_recommended_first_model() -> Result<String, String> {
-    // Recommend Parakeet V3 model for first-time users - fastest and most accurate
-    Ok("parakeet-tdt-0.6b-v3".to_string())
+pub async fn get_recommended_first_model(
+    model_manager: State<'_, Arc<ModelManager>>,
+    benchmark_manager: State<'_, Arc<BenchmarkManager>>,
+) -> Result<String, String> {
+    let models = model_manager.get_available_models();
+    Ok(benchmark_manager.recommended_model(&models))
+}
+
+#[tauri::command]
+pub async fn run_model_benchmark(
+    benchmark_manager: State<'_, Arc<BenchmarkManager>>,
+) -> Result<BenchmarkResults, String> {
+    let benchmark_manager = Arc::clone(&benchmark_manager);
+    tauri::async_runtime::spawn_blocking(move || benchmark_manager.run())
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_benchmark_results(
+    benchmark_manager: State<'_, Arc<BenchmarkManager>>,
+) -> Result<Option<BenchmarkResults>, String> {
+    Ok(benchmark_manager.results())
 }
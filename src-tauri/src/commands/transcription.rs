@@ -1,30 +1,111 @@
+use crate::audio_toolkit::decode_audio_file;
+use crate::managers::evaluation::{evaluate_corpus, EvaluationOptions, EvaluationReport};
+use crate::managers::history::HistoryManager;
 use crate::managers::transcription::TranscriptionManager;
 use crate::settings::{get_settings, write_settings, ModelUnloadTimeout};
-use tauri::{AppHandle, State};
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
 
 #[tauri::command]
-pub fn set_model_unload_timeout(app: AppHandle, timeout: ModelUnloadTimeout) {
+pub fn set_model_unload_timeout(
+    app: AppHandle,
+    transcription_manager: State<Arc<TranscriptionManager>>,
+    timeout: ModelUnloadTimeout,
+) {
     let mut settings = get_settings(&app);
     settings.model_unload_timeout = timeout;
     write_settings(&app, settings);
+
+    transcription_manager.set_unload_timeout(timeout);
 }
 
 #[tauri::command]
 pub fn get_model_load_status(
-    transcription_manager: State<TranscriptionManager>,
+    app: AppHandle,
+    transcription_manager: State<Arc<TranscriptionManager>>,
 ) -> Result<serde_json::Value, String> {
     let is_loaded = transcription_manager.is_model_loaded();
     let current_model = transcription_manager.get_current_model();
+    let loaded_models = transcription_manager.resident_models();
+    let memory_footprint_mb: u64 = loaded_models.iter().map(|m| m.size_mb).sum();
 
     Ok(serde_json::json!({
         "is_loaded": is_loaded,
-        "current_model": current_model
+        "current_model": current_model,
+        "loaded_models": loaded_models,
+        "memory_footprint_mb": memory_footprint_mb,
+        "memory_budget_mb": get_settings(&app).model_memory_budget_mb
     }))
 }
 
+/// Decodes the audio file at `path`, transcribes it like a dictation and
+/// saves the result to history. Returns the transcribed text.
+pub async fn transcribe_file_to_history(app: &AppHandle, path: PathBuf) -> Result<String> {
+    let source_file = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| anyhow!("Not an audio file: {}", path.display()))?;
+
+    let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
+    let (samples, transcription) = tauri::async_runtime::spawn_blocking(move || {
+        let samples = decode_audio_file(&path)?;
+        let transcription = tm.transcribe_standalone(samples.clone())?;
+        anyhow::Ok((samples, transcription))
+    })
+    .await??;
+
+    if transcription.text.is_empty() && transcription.filtered.is_none() {
+        return Err(anyhow!("No speech recognized in {}", source_file));
+    }
+
+    let text = transcription.text.clone();
+    let hm = app.state::<Arc<HistoryManager>>();
+    hm.save_transcription(samples, transcription, Some(source_file))
+        .await?;
+    Ok(text)
+}
+
+#[tauri::command]
+pub async fn transcribe_file(app: AppHandle, path: String) -> Result<String, String> {
+    transcribe_file_to_history(&app, PathBuf::from(path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Scores transcription of a labelled corpus with the given settings. The
+/// report is also written to `output_path` as JSON, when given.
+#[tauri::command]
+pub async fn evaluate_transcription_corpus(
+    app: AppHandle,
+    directory: String,
+    options: Option<EvaluationOptions>,
+    output_path: Option<String>,
+) -> Result<EvaluationReport, String> {
+    let report = tauri::async_runtime::spawn_blocking(move || {
+        evaluate_corpus(
+            &app,
+            &PathBuf::from(directory),
+            &options.unwrap_or_default(),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    if let Some(output_path) = output_path {
+        let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+        fs::write(&output_path, json)
+            .map_err(|e| format!("Failed to write {}: {}", output_path, e))?;
+    }
+    Ok(report)
+}
+
 #[tauri::command]
 pub fn unload_model_manually(
-    transcription_manager: State<TranscriptionManager>,
+    transcription_manager: State<Arc<TranscriptionManager>>,
 ) -> Result<(), String> {
     transcription_manager
         .unload_model()
@@ -0,0 +1,348 @@
+//! Speech-to-text engines behind a common interface.
+//!
+//! `TranscriptionManager` only talks to [`SpeechEngine`]; which implementation
+//! backs a model is decided by the model's `EngineType` through the
+//! [`EngineRegistry`].
+
+mod parakeet;
+mod remote;
+mod whisper;
+
+pub use parakeet::ParakeetSpeechEngine;
+pub use remote::RemoteSpeechEngine;
+pub use whisper::WhisperSpeechEngine;
+
+use crate::managers::model::EngineType;
+use crate::managers::transcription::{CancellationToken, TranscriptionResult};
+use crate::settings::{DecodingStrategy, ModelInferenceSettings};
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Features an engine supports. Requests for anything it lacks are rejected or
+/// left out before the engine is called.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct EngineCapabilities {
+    /// Can translate speech into English.
+    pub translation: bool,
+    /// Accepts a forced input language instead of detecting it.
+    pub language_selection: bool,
+    /// Accepts an initial prompt to bias the vocabulary.
+    pub initial_prompt: bool,
+    /// Reports timing for individual words, not only for segments.
+    pub word_timestamps: bool,
+    /// The inference settings below can be tuned per model.
+    pub thread_count: bool,
+    pub beam_search: bool,
+    pub temperature_fallback: bool,
+    pub no_speech_threshold: bool,
+}
+
+impl EngineCapabilities {
+    /// Checks that `params` only tunes what the engine supports, with values
+    /// in range.
+    pub fn validate(&self, params: &ModelInferenceSettings) -> Result<()> {
+        let unsupported = |what: &str| anyhow::anyhow!("{} is not supported by this engine", what);
+
+        if let Some(threads) = params.threads {
+            if !self.thread_count {
+                return Err(unsupported("Thread count"));
+            }
+            anyhow::ensure!(
+                (1..=MAX_THREADS).contains(&threads),
+                "Thread count must be between 1 and {}",
+                MAX_THREADS
+            );
+        }
+        if let Some(decoding) = params.decoding {
+            if !self.beam_search {
+                return Err(unsupported("Choosing the decoding strategy"));
+            }
+            if let DecodingStrategy::BeamSearch { beam_size } = decoding {
+                anyhow::ensure!(
+                    (1..=MAX_BEAM_SIZE).contains(&beam_size),
+                    "Beam size must be between 1 and {}",
+                    MAX_BEAM_SIZE
+                );
+            }
+        }
+        if params.temperature_fallback.is_some() && !self.temperature_fallback {
+            return Err(unsupported("Temperature fallback"));
+        }
+        if let Some(threshold) = params.no_speech_threshold {
+            if !self.no_speech_threshold {
+                return Err(unsupported("The no-speech threshold"));
+            }
+            anyhow::ensure!(
+                (0.0..=1.0).contains(&threshold),
+                "No-speech threshold must be between 0 and 1"
+            );
+        }
+        Ok(())
+    }
+}
+
+const MAX_THREADS: u32 = 64;
+const MAX_BEAM_SIZE: u32 = 16;
+
+/// Engine-independent parameters of a single inference call.
+#[derive(Clone, Debug, Default)]
+pub struct InferenceRequest {
+    pub language: Option<String>,
+    pub translate: bool,
+    pub initial_prompt: Option<String>,
+    /// Per-model tuning, already validated against the engine's capabilities.
+    pub params: ModelInferenceSettings,
+}
+
+pub trait SpeechEngine: Send {
+    /// Human readable engine name used in log and error messages.
+    fn name(&self) -> &'static str;
+
+    fn capabilities(&self) -> EngineCapabilities;
+
+    fn load(&mut self, model_path: &Path) -> Result<()>;
+
+    fn unload(&mut self);
+
+    /// Transcribes 16 kHz mono samples. Segment times are relative to the
+    /// start of `audio`. Engines stop as soon as they can once `cancel` is
+    /// triggered and fail with `TranscriptionCancelled`.
+    fn transcribe(
+        &mut self,
+        audio: Vec<f32>,
+        request: &InferenceRequest,
+        cancel: &CancellationToken,
+    ) -> Result<TranscriptionResult>;
+
+    /// Picks the most likely of `candidates` for the speech in `audio` from
+    /// the model's own language probabilities. Engines that can't tell return
+    /// `None`.
+    fn detect_language(
+        &mut self,
+        _audio: &[f32],
+        _candidates: &[String],
+    ) -> Result<Option<String>> {
+        Ok(None)
+    }
+}
+
+type EngineFactory = Box<dyn Fn() -> Box<dyn SpeechEngine> + Send + Sync>;
+
+/// Maps each `EngineType` to a constructor for its engine.
+pub struct EngineRegistry {
+    factories: HashMap<EngineType, EngineFactory>,
+}
+
+impl EngineRegistry {
+    /// Registry without any engines.
+    pub fn empty() -> Self {
+        Self {
+            factories: HashMap::new(),
+        }
+    }
+
+    /// Registry with every engine that needs no configuration. The remote
+    /// engine depends on the settings and is registered by its owner.
+    pub fn with_builtin_engines() -> Self {
+        let mut registry = Self::empty();
+        registry.register(EngineType::Whisper, || {
+            Box::<WhisperSpeechEngine>::default()
+        });
+        registry.register(EngineType::Parakeet, || {
+            Box::<ParakeetSpeechEngine>::default()
+        });
+        registry
+    }
+
+    /// Registers `factory` for `engine_type`, replacing any previous one.
+    pub fn register<F>(&mut self, engine_type: EngineType, factory: F)
+    where
+        F: Fn() -> Box<dyn SpeechEngine> + Send + Sync + 'static,
+    {
+        self.factories.insert(engine_type, Box::new(factory));
+    }
+
+    /// Creates a new, unloaded engine for `engine_type`.
+    pub fn create(&self, engine_type: EngineType) -> Result<Box<dyn SpeechEngine>> {
+        let factory = self.factories.get(&engine_type).ok_or_else(|| {
+            anyhow::anyhow!("No transcription engine registered for {:?}", engine_type)
+        })?;
+        Ok(factory())
+    }
+}
+
+/// Engine stand-ins for tests.
+#[cfg(test)]
+pub(crate) mod testing {
+    use super::*;
+    use crate::managers::transcription::TranscriptSegment;
+    use std::sync::{Arc, Mutex};
+
+    /// Engine that returns a fixed text and records the requests it gets.
+    pub(crate) struct FakeEngine {
+        loaded: bool,
+        text: &'static str,
+        /// Reported by `detect_language` when it is one of the candidates.
+        language: Option<&'static str>,
+        requests: Arc<Mutex<Vec<InferenceRequest>>>,
+    }
+
+    impl FakeEngine {
+        pub(crate) fn new(
+            text: &'static str,
+            language: Option<&'static str>,
+            requests: Arc<Mutex<Vec<InferenceRequest>>>,
+        ) -> Self {
+            Self {
+                loaded: false,
+                text,
+                language,
+                requests,
+            }
+        }
+    }
+
+    impl SpeechEngine for FakeEngine {
+        fn name(&self) -> &'static str {
+            "Fake"
+        }
+
+        fn capabilities(&self) -> EngineCapabilities {
+            EngineCapabilities {
+                language_selection: true,
+                initial_prompt: true,
+                ..Default::default()
+            }
+        }
+
+        fn load(&mut self, _model_path: &Path) -> Result<()> {
+            self.loaded = true;
+            Ok(())
+        }
+
+        fn unload(&mut self) {
+            self.loaded = false;
+        }
+
+        fn transcribe(
+            &mut self,
+            audio: Vec<f32>,
+            request: &InferenceRequest,
+            cancel: &CancellationToken,
+        ) -> Result<TranscriptionResult> {
+            anyhow::ensure!(self.loaded, "model not loaded");
+            cancel.check()?;
+            self.requests.lock().unwrap().push(request.clone());
+            let end = audio.len() as f32 / 16000.0;
+            Ok(TranscriptionResult {
+                text: self.text.to_string(),
+                segments: vec![TranscriptSegment {
+                    start: 0.0,
+                    end,
+                    text: self.text.to_string(),
+                    words: Vec::new(),
+                }],
+                ..Default::default()
+            })
+        }
+
+        fn detect_language(
+            &mut self,
+            _audio: &[f32],
+            candidates: &[String],
+        ) -> Result<Option<String>> {
+            Ok(self
+                .language
+                .filter(|language| candidates.iter().any(|c| c == language))
+                .map(str::to_string))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::FakeEngine;
+    use super::*;
+    use crate::managers::transcription::TranscriptionCancelled;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn registry_creates_registered_engine() -> Result<()> {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = EngineRegistry::empty();
+        registry.register(EngineType::Whisper, {
+            let requests = requests.clone();
+            move || Box::new(FakeEngine::new("fake text", None, requests.clone()))
+        });
+
+        let mut engine = registry.create(EngineType::Whisper)?;
+        assert_eq!(engine.name(), "Fake");
+        assert!(engine
+            .transcribe(
+                vec![0.0; 16000],
+                &InferenceRequest::default(),
+                &CancellationToken::default()
+            )
+            .is_err());
+
+        engine.load(Path::new("unused"))?;
+        let request = InferenceRequest {
+            initial_prompt: Some("Handy.".to_string()),
+            ..Default::default()
+        };
+        let cancel = CancellationToken::default();
+        let result = engine.transcribe(vec![0.0; 16000], &request, &cancel)?;
+        assert_eq!(result.text, "fake text");
+        assert_eq!(result.segments[0].end, 1.0);
+        assert_eq!(
+            requests.lock().unwrap()[0].initial_prompt.as_deref(),
+            Some("Handy.")
+        );
+
+        cancel.cancel();
+        let err = engine
+            .transcribe(vec![0.0; 16000], &request, &cancel)
+            .unwrap_err();
+        assert!(err.is::<TranscriptionCancelled>());
+        Ok(())
+    }
+
+    #[test]
+    fn registry_rejects_unregistered_engine() {
+        let registry = EngineRegistry::empty();
+        assert!(registry.create(EngineType::Parakeet).is_err());
+    }
+
+    #[test]
+    fn capabilities_validate_inference_settings() {
+        let capabilities = EngineCapabilities {
+            beam_search: true,
+            no_speech_threshold: true,
+            ..Default::default()
+        };
+
+        let tuned = ModelInferenceSettings {
+            decoding: Some(DecodingStrategy::BeamSearch { beam_size: 5 }),
+            no_speech_threshold: Some(0.4),
+            ..Default::default()
+        };
+        assert!(capabilities.validate(&tuned).is_ok());
+        assert!(capabilities
+            .validate(&ModelInferenceSettings::default())
+            .is_ok());
+
+        let unsupported = ModelInferenceSettings {
+            threads: Some(4),
+            ..Default::default()
+        };
+        assert!(capabilities.validate(&unsupported).is_err());
+
+        let out_of_range = ModelInferenceSettings {
+            decoding: Some(DecodingStrategy::BeamSearch { beam_size: 0 }),
+            ..Default::default()
+        };
+        assert!(capabilities.validate(&out_of_range).is_err());
+    }
+}
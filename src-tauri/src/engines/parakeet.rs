@@ -0,0 +1,105 @@
+use super::{EngineCapabilities, InferenceRequest, SpeechEngine};
+use crate::managers::transcription::{
+    group_words_into_segments, CancellationToken, TranscriptWord, TranscriptionResult,
+};
+use crate::settings::ModelPrecision;
+use anyhow::Result;
+use std::path::Path;
+use transcribe_rs::{
+    engines::parakeet::{
+        ParakeetEngine, ParakeetInferenceParams, ParakeetModelParams, TimestampGranularity,
+    },
+    TranscriptionEngine,
+};
+
+pub struct ParakeetSpeechEngine {
+    engine: ParakeetEngine,
+    precision: ModelPrecision,
+}
+
+impl ParakeetSpeechEngine {
+    pub fn new(precision: ModelPrecision) -> Self {
+        Self {
+            engine: ParakeetEngine::new(),
+            precision,
+        }
+    }
+}
+
+impl Default for ParakeetSpeechEngine {
+    fn default() -> Self {
+        Self::new(ModelPrecision::default())
+    }
+}
+
+impl SpeechEngine for ParakeetSpeechEngine {
+    fn name(&self) -> &'static str {
+        "Parakeet"
+    }
+
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities {
+            translation: false,
+            language_selection: false,
+            initial_prompt: false,
+            word_timestamps: true,
+            thread_count: false,
+            beam_search: false,
+            temperature_fallback: false,
+            no_speech_threshold: false,
+        }
+    }
+
+    fn load(&mut self, model_path: &Path) -> Result<()> {
+        let params = match self.precision {
+            ModelPrecision::Int8 => ParakeetModelParams::int8(),
+            ModelPrecision::Full => ParakeetModelParams::fp32(),
+        };
+        self.engine
+            .load_model_with_params(model_path, params)
+            .map_err(|e| anyhow::anyhow!("{} (precision {:?})", e, self.precision))
+    }
+
+    fn unload(&mut self) {
+        self.engine.unload_model();
+    }
+
+    fn transcribe(
+        &mut self,
+        audio: Vec<f32>,
+        _request: &InferenceRequest,
+        cancel: &CancellationToken,
+    ) -> Result<TranscriptionResult> {
+        let params = ParakeetInferenceParams {
+            timestamp_granularity: TimestampGranularity::Word,
+        };
+
+        let result = self
+            .engine
+            .transcribe_samples(audio, Some(params))
+            .map_err(|e| anyhow::anyhow!("Parakeet transcription failed: {}", e))?;
+        // transcribe-rs can't interrupt Parakeet, so drop the finished result
+        cancel.check()?;
+
+        // With word granularity every returned segment is one word
+        let words = result
+            .segments
+            .unwrap_or_default()
+            .into_iter()
+            .map(|s| TranscriptWord {
+                start: s.start,
+                end: s.end,
+                text: s.text.trim().to_string(),
+            })
+            .filter(|w| !w.text.is_empty())
+            .collect();
+
+        Ok(TranscriptionResult {
+            text: result.text,
+            segments: group_words_into_segments(words),
+            language: None,
+            filtered: None,
+            word_corrections: Vec::new(),
+        })
+    }
+}
@@ -0,0 +1,463 @@
+use super::{EngineCapabilities, InferenceRequest, SpeechEngine};
+use crate::managers::transcription::{
+    CancellationToken, TranscriptSegment, TranscriptionCancelled, TranscriptionResult,
+};
+use crate::settings::RemoteEngineSettings;
+use anyhow::Result;
+use hound::{WavSpec, WavWriter};
+use log::{debug, warn};
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+use std::io::Cursor;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const CONNECT_TIMEOUT_SECS: u64 = 5;
+/// Delay before the first retry; doubled for every further attempt up to
+/// `MAX_RETRY_BACKOFF`.
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+/// How often a backoff checks whether the transcription was cancelled.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Deserialize)]
+struct RemoteResponse {
+    text: String,
+    /// Only present for `verbose_json` responses.
+    #[serde(default)]
+    segments: Vec<RemoteSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteSegment {
+    start: f32,
+    end: f32,
+    text: String,
+}
+
+enum RequestError {
+    /// Timeouts, connection failures and server-side errors.
+    Retryable(anyhow::Error),
+    Fatal(anyhow::Error),
+}
+
+/// Sends recordings to an OpenAI-compatible `/v1/audio/transcriptions`
+/// endpoint, e.g. a whisper server on the LAN or a local sidecar.
+pub struct RemoteSpeechEngine {
+    config: RemoteEngineSettings,
+    client: Option<reqwest::Client>,
+}
+
+impl RemoteSpeechEngine {
+    pub fn new(config: RemoteEngineSettings) -> Self {
+        Self {
+            config,
+            client: None,
+        }
+    }
+
+    async fn post(
+        &self,
+        client: &reqwest::Client,
+        wav: &[u8],
+        request: &InferenceRequest,
+    ) -> std::result::Result<TranscriptionResult, RequestError> {
+        let file = Part::bytes(wav.to_vec())
+            .file_name("audio.wav")
+            .mime_str("audio/wav")
+            .map_err(|e| RequestError::Fatal(e.into()))?;
+        let mut form = Form::new()
+            .part("file", file)
+            .text("model", self.config.model.clone())
+            .text("response_format", "verbose_json");
+        if let Some(language) = &request.language {
+            form = form.text("language", language.clone());
+        }
+        if let Some(prompt) = &request.initial_prompt {
+            form = form.text("prompt", prompt.clone());
+        }
+
+        let mut builder = client.post(&self.config.endpoint).multipart(form);
+        if let Some(api_key) = self.config.api_key.as_deref().filter(|k| !k.is_empty()) {
+            builder = builder.bearer_auth(api_key);
+        }
+
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| RequestError::Retryable(e.into()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let error = anyhow::anyhow!("Remote server returned {}: {}", status, body.trim());
+            return Err(
+                if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                    RequestError::Retryable(error)
+                } else {
+                    RequestError::Fatal(error)
+                },
+            );
+        }
+
+        let body: RemoteResponse = response.json().await.map_err(|e| {
+            RequestError::Fatal(anyhow::anyhow!(
+                "Invalid response from remote server: {}",
+                e
+            ))
+        })?;
+
+        Ok(TranscriptionResult {
+            text: body.text,
+            segments: body
+                .segments
+                .into_iter()
+                .map(|s| TranscriptSegment {
+                    start: s.start,
+                    end: s.end,
+                    text: s.text.trim().to_string(),
+                    words: Vec::new(),
+                })
+                .collect(),
+            language: None,
+            filtered: None,
+            word_corrections: Vec::new(),
+        })
+    }
+}
+
+impl SpeechEngine for RemoteSpeechEngine {
+    fn name(&self) -> &'static str {
+        "Remote"
+    }
+
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities {
+            translation: false,
+            language_selection: true,
+            initial_prompt: true,
+            word_timestamps: false,
+            thread_count: false,
+            beam_search: false,
+            temperature_fallback: false,
+            no_speech_threshold: false,
+        }
+    }
+
+    /// Remote models have no local files, so `_model_path` is ignored. Loading
+    /// only validates the endpoint and sets up the HTTP client.
+    fn load(&mut self, _model_path: &Path) -> Result<()> {
+        let url = reqwest::Url::parse(&self.config.endpoint)
+            .map_err(|e| anyhow::anyhow!("Invalid remote endpoint: {}", e))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(anyhow::anyhow!(
+                "Remote endpoint must be an http or https URL"
+            ));
+        }
+
+        let client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS))
+            .timeout(Duration::from_secs(self.config.timeout_seconds))
+            .build()?;
+        self.client = Some(client);
+        debug!("Remote engine ready for {}", url);
+        Ok(())
+    }
+
+    fn unload(&mut self) {
+        self.client = None;
+    }
+
+    fn transcribe(
+        &mut self,
+        audio: Vec<f32>,
+        request: &InferenceRequest,
+        cancel: &CancellationToken,
+    ) -> Result<TranscriptionResult> {
+        let client = self
+            .client
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Remote engine is not loaded"))?;
+        let wav = encode_wav(&audio)?;
+
+        let mut attempt = 0;
+        loop {
+            cancel.check()?;
+            // Dropping the request on cancel closes its connection instead of
+            // waiting out the timeout
+            let posted = tauri::async_runtime::block_on(async {
+                tokio::select! {
+                    result = self.post(&client, &wav, request) => Some(result),
+                    () = cancel.cancelled() => None,
+                }
+            });
+            let result = match posted {
+                Some(result) => result,
+                None => return Err(TranscriptionCancelled.into()),
+            };
+            match result {
+                Ok(result) => return Ok(result),
+                Err(RequestError::Retryable(e)) if attempt < self.config.max_retries => {
+                    let delay = retry_delay(attempt);
+                    attempt += 1;
+                    warn!(
+                        "Remote transcription failed, retrying in {:?} ({}/{}): {}",
+                        delay, attempt, self.config.max_retries, e
+                    );
+                    sleep_unless_cancelled(delay, cancel)?;
+                }
+                Err(RequestError::Retryable(e)) | Err(RequestError::Fatal(e)) => {
+                    return Err(anyhow::anyhow!("Remote transcription failed: {}", e));
+                }
+            }
+        }
+    }
+}
+
+/// Backoff before retry number `attempt + 1`.
+fn retry_delay(attempt: u32) -> Duration {
+    let factor = 2u32.checked_pow(attempt).unwrap_or(u32::MAX);
+    RETRY_BACKOFF.saturating_mul(factor).min(MAX_RETRY_BACKOFF)
+}
+
+/// Sleeps for `delay`, failing with `TranscriptionCancelled` as soon as
+/// `cancel` is triggered.
+fn sleep_unless_cancelled(delay: Duration, cancel: &CancellationToken) -> Result<()> {
+    let deadline = Instant::now() + delay;
+    loop {
+        cancel.check()?;
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(());
+        }
+        std::thread::sleep((deadline - now).min(CANCEL_POLL_INTERVAL));
+    }
+}
+
+/// Encodes 16 kHz mono samples as a 16-bit PCM WAV file in memory.
+fn encode_wav(samples: &[f32]) -> Result<Vec<u8>> {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: 16000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut cursor = Cursor::new(Vec::new());
+    {
+        let mut writer = WavWriter::new(&mut cursor, spec)?;
+        for sample in samples {
+            writer.write_sample((sample * i16::MAX as f32) as i16)?;
+        }
+        writer.finalize()?;
+    }
+    Ok(cursor.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Minimal stand-in server answering one request per entry in `responses`
+    /// and returning the raw requests it received.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!(
+            "http://{}/v1/audio/transcriptions",
+            listener.local_addr().unwrap()
+        );
+
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut head = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    head.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut payload = vec![0; content_length];
+                reader.read_exact(&mut payload).unwrap();
+                requests.push(head + &String::from_utf8_lossy(&payload));
+
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+
+        (endpoint, handle)
+    }
+
+    fn engine(endpoint: String, max_retries: u32) -> RemoteSpeechEngine {
+        let mut engine = RemoteSpeechEngine::new(RemoteEngineSettings {
+            endpoint,
+            api_key: Some("secret".to_string()),
+            max_retries,
+            timeout_seconds: 5,
+            ..Default::default()
+        });
+        engine.load(Path::new("")).unwrap();
+        engine
+    }
+
+    #[test]
+    fn posts_wav_with_bearer_token() {
+        let (endpoint, server) = serve(vec![(
+            200,
+            r#"{"text":"Hello there.","segments":[{"start":0.0,"end":1.2,"text":" Hello there."}]}"#,
+        )]);
+        let mut engine = engine(endpoint, 0);
+
+        let request = InferenceRequest {
+            language: Some("en".to_string()),
+            ..Default::default()
+        };
+        let result = engine
+            .transcribe(vec![0.0; 16000], &request, &CancellationToken::default())
+            .unwrap();
+
+        assert_eq!(result.text, "Hello there.");
+        assert_eq!(result.segments[0].text, "Hello there.");
+        assert_eq!(result.segments[0].end, 1.2);
+
+        let requests = server.join().unwrap();
+        let sent = requests[0].to_lowercase();
+        assert!(sent.starts_with("post /v1/audio/transcriptions"));
+        assert!(sent.contains("authorization: bearer secret"));
+        assert!(sent.contains("filename=\"audio.wav\""));
+        assert!(sent.contains("riff"));
+        assert!(sent.contains("name=\"language\"\r\n\r\nen"));
+    }
+
+    #[test]
+    fn retries_server_errors() {
+        let (endpoint, server) = serve(vec![
+            (503, r#"{"error":"busy"}"#),
+            (200, r#"{"text":"ok"}"#),
+        ]);
+        let mut engine = engine(endpoint, 1);
+
+        let result = engine
+            .transcribe(
+                vec![0.0; 1600],
+                &InferenceRequest::default(),
+                &CancellationToken::default(),
+            )
+            .unwrap();
+
+        assert_eq!(result.text, "ok");
+        assert_eq!(server.join().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn does_not_retry_client_errors() {
+        let (endpoint, server) = serve(vec![(401, r#"{"error":"unauthorized"}"#)]);
+        let mut engine = engine(endpoint, 3);
+
+        let err = engine
+            .transcribe(
+                vec![0.0; 1600],
+                &InferenceRequest::default(),
+                &CancellationToken::default(),
+            )
+            .unwrap_err();
+
+        assert!(err.to_string().contains("401"));
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_cap() {
+        assert_eq!(retry_delay(0), RETRY_BACKOFF);
+        assert_eq!(retry_delay(2), RETRY_BACKOFF * 4);
+        assert_eq!(retry_delay(31), MAX_RETRY_BACKOFF);
+        assert_eq!(retry_delay(u32::MAX), MAX_RETRY_BACKOFF);
+    }
+
+    #[test]
+    fn backoff_stops_once_cancelled() {
+        let cancel = CancellationToken::default();
+        let start = Instant::now();
+        thread::spawn({
+            let cancel = cancel.clone();
+            move || {
+                thread::sleep(Duration::from_millis(50));
+                cancel.cancel();
+            }
+        });
+
+        let err = sleep_unless_cancelled(Duration::from_secs(30), &cancel).unwrap_err();
+        assert!(err.is::<TranscriptionCancelled>());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn cancelled_request_is_not_sent() {
+        let (endpoint, server) = serve(Vec::new());
+        let mut engine = engine(endpoint, 3);
+
+        let cancel = CancellationToken::default();
+        cancel.cancel();
+        let err = engine
+            .transcribe(vec![0.0; 1600], &InferenceRequest::default(), &cancel)
+            .unwrap_err();
+
+        assert!(err.is::<TranscriptionCancelled>());
+        assert!(server.join().unwrap().is_empty());
+    }
+
+    #[test]
+    fn cancel_aborts_request_in_flight() {
+        // Accepts the request but never answers it
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!(
+            "http://{}/v1/audio/transcriptions",
+            listener.local_addr().unwrap()
+        );
+        let server = thread::spawn(move || listener.accept().map(|(stream, _)| stream));
+        let mut engine = engine(endpoint, 3);
+
+        let cancel = CancellationToken::default();
+        thread::spawn({
+            let cancel = cancel.clone();
+            move || {
+                thread::sleep(Duration::from_millis(100));
+                cancel.cancel();
+            }
+        });
+
+        let start = Instant::now();
+        let err = engine
+            .transcribe(vec![0.0; 1600], &InferenceRequest::default(), &cancel)
+            .unwrap_err();
+        assert!(err.is::<TranscriptionCancelled>());
+        // Well before the 5 second request timeout
+        assert!(start.elapsed() < Duration::from_secs(2));
+        drop(server.join());
+    }
+
+    #[test]
+    fn rejects_invalid_endpoint() {
+        let mut engine = RemoteSpeechEngine::new(RemoteEngineSettings::default());
+        assert!(engine.load(Path::new("")).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use super::{EngineCapabilities, InferenceRequest, SpeechEngine};
+use crate::audio_toolkit::constants;
+use crate::managers::transcription::{CancellationToken, TranscriptSegment, TranscriptionResult};
+use crate::settings::DecodingStrategy;
+use anyhow::Result;
+use std::ffi::c_void;
+use std::path::Path;
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+/// Whisper only looks at the first 30 seconds to detect the language.
+const LANGUAGE_DETECTION_SECONDS: usize = 30;
+
+/// Runs ggml Whisper models through whisper.cpp.
+#[derive(Default)]
+pub struct WhisperSpeechEngine {
+    context: Option<WhisperContext>,
+}
+
+impl WhisperSpeechEngine {
+    fn context(&self) -> Result<&WhisperContext> {
+        self.context
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Whisper model is not loaded"))
+    }
+}
+
+/// Abort callback polled by whisper.cpp while it runs; `data` points to the
+/// `CancellationToken` of the transcription.
+unsafe extern "C" fn abort_requested(data: *mut c_void) -> bool {
+    (*(data as *const CancellationToken)).is_cancelled()
+}
+
+/// whisper.cpp's own default: all cores, but at most four.
+fn default_threads() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(4)
+}
+
+impl SpeechEngine for WhisperSpeechEngine {
+    fn name(&self) -> &'static str {
+        "Whisper"
+    }
+
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities {
+            translation: true,
+            language_selection: true,
+            initial_prompt: true,
+            word_timestamps: false,
+            thread_count: true,
+            beam_search: true,
+            temperature_fallback: true,
+            no_speech_threshold: true,
+        }
+    }
+
+    fn load(&mut self, model_path: &Path) -> Result<()> {
+        let path = model_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid model path: {}", model_path.display()))?;
+        let context = WhisperContext::new_with_params(path, WhisperContextParameters::default())
+            .map_err(|e| anyhow::anyhow!("Failed to load Whisper model: {}", e))?;
+        self.context = Some(context);
+        Ok(())
+    }
+
+    fn unload(&mut self) {
+        self.context = None;
+    }
+
+    fn transcribe(
+        &mut self,
+        audio: Vec<f32>,
+        request: &InferenceRequest,
+        cancel: &CancellationToken,
+    ) -> Result<TranscriptionResult> {
+        let context = self.context()?;
+        let mut state = context.create_state()?;
+
+        let strategy = match request.params.decoding {
+            Some(DecodingStrategy::BeamSearch { beam_size }) => SamplingStrategy::BeamSearch {
+                beam_size: beam_size as i32,
+                patience: -1.0,
+            },
+            Some(DecodingStrategy::Greedy) | None => SamplingStrategy::Greedy { best_of: 1 },
+        };
+        let mut params = FullParams::new(strategy);
+        let threads = request
+            .params
+            .threads
+            .map_or_else(default_threads, |threads| threads as usize);
+        params.set_n_threads(threads as i32);
+        params.set_language(request.language.as_deref());
+        params.set_translate(request.translate);
+        if let Some(prompt) = &request.initial_prompt {
+            params.set_initial_prompt(prompt);
+        }
+        // whisper.cpp retries at rising temperatures unless the step is zero
+        if request.params.temperature_fallback == Some(false) {
+            params.set_temperature_inc(0.0);
+        }
+        if let Some(threshold) = request.params.no_speech_threshold {
+            params.set_no_speech_thold(threshold);
+        }
+        params.set_print_special(false);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+        // whisper-rs' closure-based abort callback casts its user data to the
+        // wrong type, so point the raw callback at the token instead.
+        // SAFETY: `cancel` is borrowed for this whole call, and whisper.cpp
+        // only invokes the callback from within `state.full` below. `params`
+        // is consumed by that call, so the pointer cannot be used after it
+        // returns. `is_cancelled` only reads an atomic, so sharing the token
+        // with the decoding threads is sound.
+        unsafe {
+            params.set_abort_callback(Some(abort_requested));
+            params.set_abort_callback_user_data(cancel as *const CancellationToken as *mut c_void);
+        }
+
+        let outcome = state.full(params, &audio);
+        // An aborted run fails or stops early; either way report the cancel
+        cancel.check()?;
+        outcome.map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))?;
+
+        // Whisper only reports segment-level timing, in centiseconds
+        let mut text = String::new();
+        let mut segments = Vec::new();
+        for i in 0..state.full_n_segments()? {
+            let segment_text = state.full_get_segment_text_lossy(i)?;
+            text.push_str(&segment_text);
+            segments.push(TranscriptSegment {
+                start: state.full_get_segment_t0(i)? as f32 / 100.0,
+                end: state.full_get_segment_t1(i)? as f32 / 100.0,
+                text: segment_text.trim().to_string(),
+                words: Vec::new(),
+            });
+        }
+
+        // The language Whisper settled on, unless the text is a translation
+        let language = if request.translate {
+            None
+        } else {
+            match &request.language {
+                Some(language) => Some(language.clone()),
+                None => {
+                    whisper_rs::get_lang_str(state.full_lang_id_from_state()?).map(str::to_string)
+                }
+            }
+        };
+
+        Ok(TranscriptionResult {
+            text: text.trim().to_string(),
+            segments,
+            language,
+            filtered: None,
+            word_corrections: Vec::new(),
+        })
+    }
+
+    fn detect_language(&mut self, audio: &[f32], candidates: &[String]) -> Result<Option<String>> {
+        let context = self.context()?;
+        let mut state = context.create_state()?;
+        let threads = default_threads();
+
+        let window = LANGUAGE_DETECTION_SECONDS * constants::WHISPER_SAMPLE_RATE as usize;
+        state.pcm_to_mel(&audio[..audio.len().min(window)], threads)?;
+        let (_, probabilities) = state.lang_detect(0, threads)?;
+
+        Ok(candidates
+            .iter()
+            .filter_map(|language| {
+                let id = whisper_rs::get_lang_id(language)?;
+                Some((language, *probabilities.get(id as usize)?))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(language, _)| language.clone()))
+    }
+}
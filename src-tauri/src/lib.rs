@@ -3,6 +3,7 @@ mod audio_feedback;
 pub mod audio_toolkit;
 mod clipboard;
 mod commands;
+mod engines;
 mod managers;
 mod overlay;
 mod settings;
@@ -11,10 +12,14 @@ mod tray;
 mod utils;
 
 use managers::audio::AudioRecordingManager;
+use managers::batch::BatchManager;
+use managers::benchmark::BenchmarkManager;
 use managers::history::HistoryManager;
 use managers::model::ModelManager;
+use managers::queue::TranscriptionQueue;
 use managers::transcription::TranscriptionManager;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use tauri::image::Image;
 
@@ -53,6 +58,103 @@ fn show_main_window(app: &AppHandle) {
     }
 }
 
+/// Work run from the command line instead of the GUI.
+enum CliCommand {
+    /// `--transcribe-file <path>`: saves the text to history and prints it.
+    TranscribeFile(PathBuf),
+    /// `--evaluate-corpus <directory> [--model <id>]`: prints the evaluation
+    /// report as JSON.
+    EvaluateCorpus(PathBuf, Option<String>),
+}
+
+/// The argument following `flag`.
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a String> {
+    let index = args.iter().position(|arg| arg == flag)?;
+    args.get(index + 1)
+}
+
+/// The command given in `args`, with paths resolved against `cwd`.
+fn cli_command(args: &[String], cwd: &Path) -> Option<CliCommand> {
+    if let Some(path) = flag_value(args, "--transcribe-file") {
+        return Some(CliCommand::TranscribeFile(cwd.join(path)));
+    }
+    flag_value(args, "--evaluate-corpus").map(|directory| {
+        CliCommand::EvaluateCorpus(cwd.join(directory), flag_value(args, "--model").cloned())
+    })
+}
+
+/// Runs a command line command without the GUI, tray or shortcuts, and
+/// without handing over to a running instance. Returns the process exit
+/// code.
+fn run_cli_command(mut context: tauri::Context<tauri::Wry>, command: CliCommand) -> i32 {
+    context.config_mut().app.windows.clear();
+    let app = match tauri::Builder::default()
+        .plugin(tauri_plugin_store::Builder::default().build())
+        .build(context)
+    {
+        Ok(app) => app,
+        Err(e) => {
+            eprintln!("Failed to start Handy: {}", e);
+            return 1;
+        }
+    };
+    #[cfg(target_os = "macos")]
+    let _ = app.set_activation_policy(tauri::ActivationPolicy::Accessory);
+
+    if let Err(e) = manage_transcription_state(&app) {
+        eprintln!("Failed to initialize transcription: {}", e);
+        return 1;
+    }
+
+    match command {
+        CliCommand::TranscribeFile(path) => {
+            let transcription = tauri::async_runtime::block_on(
+                commands::transcription::transcribe_file_to_history(app.handle(), path.clone()),
+            );
+            match transcription {
+                Ok(text) => {
+                    println!("{}", text);
+                    0
+                }
+                Err(e) => {
+                    eprintln!("Failed to transcribe {}: {}", path.display(), e);
+                    1
+                }
+            }
+        }
+        CliCommand::EvaluateCorpus(directory, model_id) => {
+            let options = managers::evaluation::EvaluationOptions {
+                model_id,
+                ..Default::default()
+            };
+            let report = managers::evaluation::evaluate_corpus(app.handle(), &directory, &options)
+                .and_then(|report| Ok(serde_json::to_string_pretty(&report)?));
+            match report {
+                Ok(json) => {
+                    println!("{}", json);
+                    0
+                }
+                Err(e) => {
+                    eprintln!("Failed to evaluate {}: {:#}", directory.display(), e);
+                    1
+                }
+            }
+        }
+    }
+}
+
+/// The managers command line transcription needs, without audio, tray or
+/// queues.
+fn manage_transcription_state(app: &tauri::App) -> anyhow::Result<()> {
+    let model_manager = Arc::new(ModelManager::new(app)?);
+    let transcription_manager = Arc::new(TranscriptionManager::new(app, model_manager.clone())?);
+    let history_manager = Arc::new(HistoryManager::new(app)?);
+    app.manage(model_manager);
+    app.manage(transcription_manager);
+    app.manage(history_manager);
+    Ok(())
+}
+
 #[tauri::command]
 fn trigger_update_check(app: AppHandle) -> Result<(), String> {
     app.emit("check-for-updates", ())
@@ -64,6 +166,15 @@ fn trigger_update_check(app: AppHandle) -> Result<(), String> {
 pub fn run() {
     env_logger::init();
 
+    let context = tauri::generate_context!();
+    let args: Vec<String> = std::env::args().collect();
+    let cwd = std::env::current_dir().unwrap_or_default();
+    if let Some(command) = cli_command(&args, &cwd) {
+        let code = run_cli_command(context, command);
+        let _ = std::io::Write::flush(&mut std::io::stdout());
+        std::process::exit(code);
+    }
+
     tauri::Builder::default()
         .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
             show_main_window(app);
@@ -163,12 +274,20 @@ pub fn run() {
             );
             let history_manager =
                 Arc::new(HistoryManager::new(app).expect("Failed to initialize history manager"));
+            let transcription_queue = Arc::new(TranscriptionQueue::new(app));
+            let batch_manager = Arc::new(BatchManager::new(app));
+            let benchmark_manager = Arc::new(
+                BenchmarkManager::new(app).expect("Failed to initialize benchmark manager"),
+            );
 
             // Add managers to Tauri's managed state
             app.manage(recording_manager.clone());
             app.manage(model_manager.clone());
             app.manage(transcription_manager.clone());
             app.manage(history_manager.clone());
+            app.manage(transcription_queue);
+            app.manage(batch_manager);
+            app.manage(benchmark_manager);
 
             // Create the recording overlay window (hidden by default)
             utils::create_recording_overlay(app.handle());
@@ -210,6 +329,20 @@ pub fn run() {
             shortcut::change_debug_mode_setting,
             shortcut::change_word_correction_threshold_setting,
             shortcut::update_custom_words,
+            shortcut::update_protected_words,
+            shortcut::change_protect_frequent_words_setting,
+            shortcut::change_replacement_rules_setting,
+            shortcut::import_replacement_rules,
+            shortcut::export_replacement_rules,
+            shortcut::change_language_candidates_setting,
+            shortcut::change_prompt_context_setting,
+            shortcut::change_remote_engine_settings,
+            shortcut::change_model_memory_budget_setting,
+            shortcut::change_speech_gate_settings,
+            shortcut::change_spoken_punctuation_settings,
+            shortcut::change_context_carry_over_setting,
+            shortcut::change_context_carry_over_idle_setting,
+            shortcut::change_max_chunk_seconds_setting,
             shortcut::suspend_binding,
             shortcut::resume_binding,
             trigger_update_check,
@@ -221,12 +354,17 @@ pub fn run() {
             commands::models::delete_model,
             commands::models::cancel_download,
             commands::models::set_active_model,
+            commands::models::get_model_inference_capabilities,
+            commands::models::set_model_inference_settings,
+            commands::models::set_parakeet_precision,
             commands::models::get_current_model,
             commands::models::get_transcription_model_status,
             commands::models::is_model_loading,
             commands::models::has_any_models_available,
             commands::models::has_any_models_or_downloads,
             commands::models::get_recommended_first_model,
+            commands::models::run_model_benchmark,
+            commands::models::get_benchmark_results,
             commands::audio::update_microphone_mode,
             commands::audio::get_microphone_mode,
             commands::audio::get_available_microphones,
@@ -238,11 +376,18 @@ pub fn run() {
             commands::transcription::set_model_unload_timeout,
             commands::transcription::get_model_load_status,
             commands::transcription::unload_model_manually,
+            commands::transcription::transcribe_file,
+            commands::transcription::evaluate_transcription_corpus,
+            commands::batch::start_batch_transcription,
+            commands::batch::pause_batch_transcription,
+            commands::batch::resume_batch_transcription,
+            commands::batch::get_batch_status,
             commands::history::get_history_entries,
             commands::history::toggle_history_entry_saved,
+            commands::history::get_history_segments,
             commands::history::stream_history_audio,
             commands::history::delete_history_entry
         ])
-        .run(tauri::generate_context!())
+        .run(context)
         .expect("error while running tauri application");
 }
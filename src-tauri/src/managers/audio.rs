@@ -1,5 +1,8 @@
-use crate::audio_toolkit::{list_input_devices, vad::SmoothedVad, AudioRecorder, SileroVad};
-use crate::settings::get_settings;
+use crate::audio_toolkit::{
+    list_input_devices, vad::SmoothedVad, AudioRecorder, Recording, SileroVad,
+};
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings::{get_settings, SpeechGateSettings};
 use crate::utils;
 use log::{debug, info};
 use std::sync::{Arc, Mutex};
@@ -33,7 +36,7 @@ fn create_audio_recorder(
     let smoothed_vad = SmoothedVad::new(Box::new(silero), 15, 15, 2);
 
     // Recorder with VAD plus a spectrum-level callback that forwards updates to
-    // the frontend.
+    // the frontend, and a segment callback that feeds streaming transcription.
     let recorder = AudioRecorder::new()
         .map_err(|e| anyhow::anyhow!("Failed to create AudioRecorder: {}", e))?
         .with_vad(Box::new(smoothed_vad))
@@ -42,6 +45,14 @@ fn create_audio_recorder(
             move |levels| {
                 utils::emit_levels(&app_handle, &levels);
             }
+        })
+        .with_segment_callback({
+            let app_handle = app_handle.clone();
+            move |segment| {
+                if let Some(tm) = app_handle.try_state::<Arc<TranscriptionManager>>() {
+                    tm.push_stream_segment(segment);
+                }
+            }
         });
 
     Ok(recorder)
@@ -231,7 +242,7 @@ impl AudioRecordingManager {
         Ok(())
     }
 
-    pub fn stop_recording(&self, binding_id: &str) -> Option<Vec<f32>> {
+    pub fn stop_recording(&self, binding_id: &str) -> Option<Recording> {
         let mut state = self.state.lock().unwrap();
 
         match *state {
@@ -241,17 +252,17 @@ impl AudioRecordingManager {
                 *state = RecordingState::Idle;
                 drop(state);
 
-                let samples = if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+                let recording = if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
                     match rec.stop() {
-                        Ok(buf) => buf,
+                        Ok(recording) => recording,
                         Err(e) => {
                             eprintln!("stop() failed: {e}");
-                            Vec::new()
+                            Recording::default()
                         }
                     }
                 } else {
                     eprintln!("Recorder not available");
-                    Vec::new()
+                    Recording::default()
                 };
 
                 *self.is_recording.lock().unwrap() = false;
@@ -261,21 +272,19 @@ impl AudioRecordingManager {
                     self.stop_microphone_stream();
                 }
 
-                // Pad if very short
-                let s_len = samples.len();
-                // println!("Got {} samples", { s_len });
-                if s_len < WHISPER_SAMPLE_RATE && s_len > 0 {
-                    let mut padded = samples;
-                    padded.resize(WHISPER_SAMPLE_RATE * 5 / 4, 0.0);
-                    Some(padded)
-                } else {
-                    Some(samples)
-                }
+                Some(recording)
             }
             _ => None,
         }
     }
 
+    pub fn is_recording(&self) -> bool {
+        matches!(
+            *self.state.lock().unwrap(),
+            RecordingState::Recording { .. }
+        )
+    }
+
     /// Cancel any ongoing recording without returning audio samples
     pub fn cancel_recording(&self) {
         let mut state = self.state.lock().unwrap();
@@ -297,3 +306,79 @@ impl AudioRecordingManager {
         }
     }
 }
+
+/// Why a recording was discarded without being transcribed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NothingHeard {
+    TooShort,
+    MostlyNoise,
+    TooQuiet,
+}
+
+/// Checks a stopped recording against the speech gate, so accidental taps and
+/// silent recordings never reach the model.
+pub fn check_speech_gate(
+    recording: &Recording,
+    gate: &SpeechGateSettings,
+) -> Result<(), NothingHeard> {
+    if recording.speech_seconds() * 1000.0 < gate.min_speech_ms as f32 {
+        return Err(NothingHeard::TooShort);
+    }
+    if recording.speech_ratio() < gate.min_speech_ratio {
+        return Err(NothingHeard::MostlyNoise);
+    }
+    if recording.rms() < gate.min_rms {
+        return Err(NothingHeard::TooQuiet);
+    }
+    Ok(())
+}
+
+/// Pads recordings shorter than a second with silence, which the models
+/// handle better than very short input.
+pub fn pad_short_samples(mut samples: Vec<f32>) -> Vec<f32> {
+    if samples.len() < WHISPER_SAMPLE_RATE && !samples.is_empty() {
+        samples.resize(WHISPER_SAMPLE_RATE * 5 / 4, 0.0);
+    }
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording(speech_seconds: f32, total_seconds: f32, level: f32) -> Recording {
+        Recording {
+            samples: vec![level; (speech_seconds * WHISPER_SAMPLE_RATE as f32) as usize],
+            total_samples: (total_seconds * WHISPER_SAMPLE_RATE as f32) as usize,
+        }
+    }
+
+    #[test]
+    fn speech_gate_rejects_taps_and_silence() {
+        let gate = SpeechGateSettings::default();
+
+        assert_eq!(check_speech_gate(&recording(2.0, 3.0, 0.1), &gate), Ok(()));
+        assert_eq!(
+            check_speech_gate(&recording(0.0, 0.4, 0.0), &gate),
+            Err(NothingHeard::TooShort)
+        );
+        assert_eq!(
+            check_speech_gate(&recording(0.5, 120.0, 0.1), &gate),
+            Err(NothingHeard::MostlyNoise)
+        );
+        assert_eq!(
+            check_speech_gate(&recording(2.0, 3.0, 0.0001), &gate),
+            Err(NothingHeard::TooQuiet)
+        );
+    }
+
+    #[test]
+    fn speech_gate_thresholds_can_be_disabled() {
+        let gate = SpeechGateSettings {
+            min_speech_ms: 0,
+            min_rms: 0.0,
+            min_speech_ratio: 0.0,
+        };
+        assert_eq!(check_speech_gate(&Recording::default(), &gate), Ok(()));
+    }
+}
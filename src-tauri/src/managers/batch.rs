@@ -0,0 +1,381 @@
+use crate::audio_toolkit::decode_audio_file;
+use crate::managers::transcription::{TranscriptionManager, TranscriptionResult};
+use anyhow::{bail, Context, Result};
+use log::{debug, error};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use tauri::{App, AppHandle, Emitter, Manager};
+
+/// Extensions of the audio files picked up from a batch directory.
+const AUDIO_EXTENSIONS: &[&str] = &["wav", "flac", "mp3", "ogg"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchFileStatus {
+    Transcribing,
+    Done,
+    Failed,
+}
+
+/// Emitted as `batch-progress` whenever a file starts or finishes.
+#[derive(Clone, Debug, Serialize)]
+pub struct BatchProgress {
+    pub batch_id: u64,
+    pub file: String,
+    /// Position of the file in its batch, starting at 1.
+    pub index: usize,
+    pub total: usize,
+    pub status: BatchFileStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BatchFailure {
+    pub file: String,
+    pub error: String,
+}
+
+/// Emitted as `batch-finished` once every file of a batch was attempted.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BatchSummary {
+    pub batch_id: u64,
+    pub directory: String,
+    pub total: usize,
+    pub succeeded: usize,
+    pub failures: Vec<BatchFailure>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BatchStatus {
+    pub paused: bool,
+    /// Files waiting to be transcribed, across all batches.
+    pub pending: usize,
+    /// File being transcribed right now, if any.
+    pub active_file: Option<String>,
+}
+
+struct BatchFile {
+    batch_id: u64,
+    path: PathBuf,
+    index: usize,
+}
+
+#[derive(Default)]
+struct BatchState {
+    queue: VecDeque<BatchFile>,
+    /// Summaries of the batches that still have files queued or running.
+    running: HashMap<u64, BatchSummary>,
+    active_file: Option<PathBuf>,
+    paused: bool,
+    shutdown: bool,
+    next_id: u64,
+}
+
+impl BatchState {
+    /// Queues `files` as a new batch and returns its id.
+    fn add_batch(&mut self, directory: &Path, files: Vec<PathBuf>) -> u64 {
+        self.next_id += 1;
+        let batch_id = self.next_id;
+        self.running.insert(
+            batch_id,
+            BatchSummary {
+                batch_id,
+                directory: directory.display().to_string(),
+                total: files.len(),
+                succeeded: 0,
+                failures: Vec::new(),
+            },
+        );
+        self.queue
+            .extend(files.into_iter().enumerate().map(|(i, path)| BatchFile {
+                batch_id,
+                path,
+                index: i + 1,
+            }));
+        batch_id
+    }
+
+    /// Records the outcome of a file. Returns the batch summary once this
+    /// was its last file.
+    fn record(&mut self, file: &BatchFile, outcome: Result<(), String>) -> Option<BatchSummary> {
+        self.active_file = None;
+        let summary = self.running.get_mut(&file.batch_id)?;
+        match outcome {
+            Ok(()) => summary.succeeded += 1,
+            Err(error) => summary.failures.push(BatchFailure {
+                file: file_label(&file.path),
+                error,
+            }),
+        }
+        if summary.succeeded + summary.failures.len() < summary.total {
+            return None;
+        }
+        self.running.remove(&file.batch_id)
+    }
+}
+
+/// Transcribes every audio file of a directory on a dedicated thread, one
+/// file at a time, and writes `<file>.txt` and `<file>.json` next to each
+/// one. Batches queue up behind each other and can be paused between files.
+/// A file that fails is reported in the batch summary and skipped.
+pub struct BatchManager {
+    shared: Arc<(Mutex<BatchState>, Condvar)>,
+}
+
+impl BatchManager {
+    pub fn new(app: &App) -> Self {
+        let shared = Arc::new((Mutex::new(BatchState::default()), Condvar::new()));
+
+        let app_handle = app.app_handle().clone();
+        let worker_shared = shared.clone();
+        thread::spawn(move || {
+            while let Some(file) = next_file(&worker_shared) {
+                process_file(&app_handle, &worker_shared, file);
+            }
+            debug!("Batch transcription worker stopped");
+        });
+
+        Self { shared }
+    }
+
+    /// Queues every audio file in `directory` and returns the new batch id,
+    /// along with the number of files found.
+    pub fn start_batch(&self, directory: &Path) -> Result<(u64, usize)> {
+        let files = collect_audio_files(directory)?;
+        if files.is_empty() {
+            bail!("No audio files found in {}", directory.display());
+        }
+        let count = files.len();
+
+        let (state, changed) = &*self.shared;
+        let batch_id = state.lock().unwrap().add_batch(directory, files);
+        changed.notify_all();
+        debug!(
+            "Queued batch {} with {} files from {}",
+            batch_id,
+            count,
+            directory.display()
+        );
+        Ok((batch_id, count))
+    }
+
+    /// Stops picking up new files. The file being transcribed still finishes.
+    pub fn pause(&self) {
+        self.set_paused(true);
+    }
+
+    pub fn resume(&self) {
+        self.set_paused(false);
+    }
+
+    pub fn status(&self) -> BatchStatus {
+        let state = self.shared.0.lock().unwrap();
+        BatchStatus {
+            paused: state.paused,
+            pending: state.queue.len(),
+            active_file: state.active_file.as_deref().map(file_label),
+        }
+    }
+
+    fn set_paused(&self, paused: bool) {
+        let (state, changed) = &*self.shared;
+        state.lock().unwrap().paused = paused;
+        changed.notify_all();
+    }
+}
+
+impl Drop for BatchManager {
+    fn drop(&mut self) {
+        debug!("Shutting down BatchManager");
+        // Not joined: the worker may be in the middle of a long file. It exits
+        // once that file is done.
+        let (state, changed) = &*self.shared;
+        state.lock().unwrap().shutdown = true;
+        changed.notify_all();
+    }
+}
+
+/// Blocks until a file is queued and the batch is not paused. Returns `None`
+/// once the manager shuts down.
+fn next_file(shared: &(Mutex<BatchState>, Condvar)) -> Option<BatchFile> {
+    let (state, changed) = shared;
+    let mut state = state.lock().unwrap();
+    loop {
+        if state.shutdown {
+            return None;
+        }
+        if !state.paused {
+            if let Some(file) = state.queue.pop_front() {
+                state.active_file = Some(file.path.clone());
+                return Some(file);
+            }
+        }
+        state = changed.wait(state).unwrap();
+    }
+}
+
+fn process_file(app_handle: &AppHandle, shared: &(Mutex<BatchState>, Condvar), file: BatchFile) {
+    let total = shared
+        .0
+        .lock()
+        .unwrap()
+        .running
+        .get(&file.batch_id)
+        .map_or(0, |summary| summary.total);
+    let progress = |status, error| BatchProgress {
+        batch_id: file.batch_id,
+        file: file_label(&file.path),
+        index: file.index,
+        total,
+        status,
+        error,
+    };
+    let _ = app_handle.emit(
+        "batch-progress",
+        progress(BatchFileStatus::Transcribing, None),
+    );
+
+    let outcome = transcribe_file(app_handle, &file.path).map_err(|e| format!("{:#}", e));
+    let event = match &outcome {
+        Ok(()) => progress(BatchFileStatus::Done, None),
+        Err(e) => {
+            error!(
+                "Batch transcription of {} failed: {}",
+                file.path.display(),
+                e
+            );
+            progress(BatchFileStatus::Failed, Some(e.clone()))
+        }
+    };
+    let _ = app_handle.emit("batch-progress", event);
+
+    let summary = shared.0.lock().unwrap().record(&file, outcome);
+    if let Some(summary) = summary {
+        debug!(
+            "Batch {} finished: {} of {} files transcribed",
+            summary.batch_id, summary.succeeded, summary.total
+        );
+        let _ = app_handle.emit("batch-finished", summary);
+    }
+}
+
+fn transcribe_file(app_handle: &AppHandle, path: &Path) -> Result<()> {
+    let samples = decode_audio_file(path)?;
+    let tm = app_handle.state::<Arc<TranscriptionManager>>();
+    let result = tm.transcribe_standalone(samples)?;
+    write_sidecars(path, &result)
+}
+
+/// Audio files directly inside `directory`, sorted by name.
+pub fn collect_audio_files(directory: &Path) -> Result<Vec<PathBuf>> {
+    let entries = fs::read_dir(directory)
+        .with_context(|| format!("Failed to read {}", directory.display()))?;
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let is_audio = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| AUDIO_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()));
+        if is_audio && path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// `memo.wav` gets `memo.wav.txt` and `memo.wav.json`, so recordings that
+/// only differ in their extension do not overwrite each other's transcripts.
+fn sidecar_path(audio: &Path, extension: &str) -> PathBuf {
+    let mut name = audio.as_os_str().to_owned();
+    name.push(".");
+    name.push(extension);
+    PathBuf::from(name)
+}
+
+fn write_sidecars(audio: &Path, result: &TranscriptionResult) -> Result<()> {
+    let txt = sidecar_path(audio, "txt");
+    fs::write(&txt, &result.text).with_context(|| format!("Failed to write {}", txt.display()))?;
+
+    let json = sidecar_path(audio, "json");
+    fs::write(&json, serde_json::to_string_pretty(result)?)
+        .with_context(|| format!("Failed to write {}", json.display()))?;
+    Ok(())
+}
+
+fn file_label(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_audio_files_filters_and_sorts() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["b.MP3", "a.wav", "notes.txt", "a.wav.json", "c.flac"] {
+            fs::write(dir.path().join(name), b"").unwrap();
+        }
+        fs::create_dir(dir.path().join("nested.ogg")).unwrap();
+
+        let files = collect_audio_files(dir.path()).unwrap();
+        let names: Vec<String> = files.iter().map(|f| file_label(f)).collect();
+        assert_eq!(names, vec!["a.wav", "b.MP3", "c.flac"]);
+
+        assert!(collect_audio_files(&dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn sidecars_are_written_next_to_the_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let audio = dir.path().join("memo.wav");
+        let result = TranscriptionResult {
+            text: "Call the dentist.".to_string(),
+            ..Default::default()
+        };
+
+        write_sidecars(&audio, &result).unwrap();
+
+        let txt = fs::read_to_string(dir.path().join("memo.wav.txt")).unwrap();
+        assert_eq!(txt, "Call the dentist.");
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.path().join("memo.wav.json")).unwrap())
+                .unwrap();
+        assert_eq!(json["text"], "Call the dentist.");
+    }
+
+    #[test]
+    fn batch_summary_collects_failures() {
+        let mut state = BatchState::default();
+        let files = vec![PathBuf::from("/memos/a.wav"), PathBuf::from("/memos/b.wav")];
+        let batch_id = state.add_batch(Path::new("/memos"), files);
+        assert_eq!(state.queue.len(), 2);
+
+        let first = state.queue.pop_front().unwrap();
+        assert_eq!(state.record(&first, Err("bad header".to_string())), None);
+
+        let second = state.queue.pop_front().unwrap();
+        let summary = state.record(&second, Ok(())).unwrap();
+        assert_eq!(summary.batch_id, batch_id);
+        assert_eq!(summary.total, 2);
+        assert_eq!(summary.succeeded, 1);
+        assert_eq!(
+            summary.failures,
+            vec![BatchFailure {
+                file: "a.wav".to_string(),
+                error: "bad header".to_string(),
+            }]
+        );
+        assert!(state.running.is_empty());
+    }
+}
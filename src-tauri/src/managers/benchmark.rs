@@ -0,0 +1,326 @@
+use crate::audio_toolkit::{constants, decode_audio_file};
+use crate::managers::model::{EngineType, ModelInfo, ModelManager};
+use crate::managers::transcription::TranscriptionManager;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use log::{debug, error, warn};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{App, AppHandle, Emitter, Manager};
+
+const RESULTS_FILE: &str = "benchmark.json";
+/// About 18 seconds of 16 kHz mono speech-like audio. Only the timing is
+/// measured, so the clip needs no reference transcript.
+const REFERENCE_CLIP: &str = "resources/benchmark.wav";
+
+/// Recommended for first runs when nothing is known about the machine.
+const DEFAULT_MODEL: &str = "parakeet-tdt-0.6b-v3";
+
+/// Local models from the most to the least accurate.
+const QUALITY_ORDER: &[&str] = &["parakeet-tdt-0.6b-v3", "large", "turbo", "medium", "small"];
+
+/// Dictation feels instant when ten seconds of speech take two to transcribe.
+const TARGET_REAL_TIME_FACTOR: f32 = 0.2;
+
+/// Without measurements, a model needs this many times its size in RAM and,
+/// from `LARGE_MODEL_MB` on, at least `LARGE_MODEL_THREADS` CPU threads.
+const MEMORY_HEADROOM: u64 = 4;
+const LARGE_MODEL_MB: u64 = 800;
+const LARGE_MODEL_THREADS: usize = 8;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct HardwareInfo {
+    pub cpu_threads: usize,
+    /// Installed memory, 0 when it could not be read.
+    pub total_memory_mb: u64,
+}
+
+impl HardwareInfo {
+    pub fn probe() -> Self {
+        let cpu_threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+
+        let mut system = sysinfo::System::new();
+        system.refresh_memory();
+
+        Self {
+            cpu_threads,
+            total_memory_mb: system.total_memory() / (1024 * 1024),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModelBenchmark {
+    pub model_id: String,
+    /// Time to load the model from disk; 0 if it was already resident
+    /// whenever it was measured.
+    pub load_ms: u64,
+    pub transcribe_ms: u64,
+    pub clip_seconds: f32,
+    /// Transcription time divided by the clip length; lower is faster.
+    pub real_time_factor: f32,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BenchmarkResults {
+    pub hardware: HardwareInfo,
+    /// Unix timestamp of the last run.
+    pub measured_at: i64,
+    pub models: Vec<ModelBenchmark>,
+}
+
+/// Emitted as `benchmark-progress` before each model is measured.
+#[derive(Clone, Debug, Serialize)]
+pub struct BenchmarkProgress {
+    pub model_id: String,
+    /// Position of the model in this run, starting at 1.
+    pub index: usize,
+    pub total: usize,
+}
+
+/// Measures how fast the downloaded models run on this machine and keeps the
+/// results, so the recommended model and the expected latency in the model
+/// picker reflect this specific hardware.
+pub struct BenchmarkManager {
+    app_handle: AppHandle,
+    results_path: PathBuf,
+    results: Mutex<Option<BenchmarkResults>>,
+}
+
+impl BenchmarkManager {
+    pub fn new(app: &App) -> Result<Self> {
+        let results_path = app.path().app_data_dir()?.join(RESULTS_FILE);
+
+        let results = match fs::read_to_string(&results_path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| warn!("Ignoring unreadable benchmark results: {}", e))
+                .ok(),
+            Err(_) => None,
+        };
+
+        Ok(Self {
+            app_handle: app.app_handle().clone(),
+            results_path,
+            results: Mutex::new(results),
+        })
+    }
+
+    pub fn results(&self) -> Option<BenchmarkResults> {
+        self.results.lock().unwrap().clone()
+    }
+
+    /// Runs every downloaded local model on the bundled reference clip and
+    /// stores the results. A model that fails is logged and left out. Blocks
+    /// until all models were measured.
+    pub fn run(&self) -> Result<BenchmarkResults> {
+        let clip_path = self
+            .app_handle
+            .path()
+            .resolve(REFERENCE_CLIP, tauri::path::BaseDirectory::Resource)?;
+        let clip = decode_audio_file(&clip_path).context("Failed to read the benchmark clip")?;
+        let clip_seconds = clip.len() as f32 / constants::WHISPER_SAMPLE_RATE as f32;
+
+        let mm = self.app_handle.state::<Arc<ModelManager>>();
+        let tm = self.app_handle.state::<Arc<TranscriptionManager>>();
+        let models: Vec<ModelInfo> = mm
+            .get_available_models()
+            .into_iter()
+            .filter(|m| m.is_downloaded && m.engine_type != EngineType::Remote)
+            .collect();
+
+        // A resident model is measured without loading it, so its load time
+        // carries over from the previous run
+        let previous = self.results();
+        let previous_load_ms = |model_id: &str| {
+            previous
+                .iter()
+                .flat_map(|results| &results.models)
+                .find(|b| b.model_id == model_id)
+                .map_or(0, |b| b.load_ms)
+        };
+
+        let mut results = BenchmarkResults {
+            hardware: HardwareInfo::probe(),
+            measured_at: Utc::now().timestamp(),
+            models: Vec::new(),
+        };
+        for (i, model) in models.iter().enumerate() {
+            let _ = self.app_handle.emit(
+                "benchmark-progress",
+                BenchmarkProgress {
+                    model_id: model.id.clone(),
+                    index: i + 1,
+                    total: models.len(),
+                },
+            );
+
+            match tm.measure_model(&model.id, &clip) {
+                Ok((load_time, transcribe_time)) => {
+                    let benchmark = ModelBenchmark {
+                        model_id: model.id.clone(),
+                        load_ms: match load_time {
+                            Some(load_time) => load_time.as_millis() as u64,
+                            None => previous_load_ms(&model.id),
+                        },
+                        transcribe_ms: transcribe_time.as_millis() as u64,
+                        clip_seconds,
+                        real_time_factor: transcribe_time.as_secs_f32() / clip_seconds,
+                    };
+                    debug!("Benchmarked {:?}", benchmark);
+                    results.models.push(benchmark);
+                }
+                Err(e) => error!("Failed to benchmark {}: {}", model.id, e),
+            }
+        }
+
+        fs::write(&self.results_path, serde_json::to_string_pretty(&results)?)
+            .with_context(|| format!("Failed to write {}", self.results_path.display()))?;
+        *self.results.lock().unwrap() = Some(results.clone());
+        Ok(results)
+    }
+
+    /// The model to suggest on this machine, out of `models`.
+    pub fn recommended_model(&self, models: &[ModelInfo]) -> String {
+        let results = self.results();
+        let (hardware, benchmarks) = match &results {
+            Some(results) => (results.hardware.clone(), results.models.as_slice()),
+            None => (HardwareInfo::probe(), &[][..]),
+        };
+        recommend_model(models, &hardware, benchmarks).unwrap_or_else(|| DEFAULT_MODEL.to_string())
+    }
+}
+
+/// Picks the most accurate model that is fast enough. Measured speeds take
+/// precedence; without any, the model must fit the memory and CPU threads.
+fn recommend_model(
+    models: &[ModelInfo],
+    hardware: &HardwareInfo,
+    benchmarks: &[ModelBenchmark],
+) -> Option<String> {
+    let by_quality = || {
+        QUALITY_ORDER
+            .iter()
+            .filter_map(|id| models.iter().find(|m| m.id == *id))
+    };
+
+    if !benchmarks.is_empty() {
+        let measured = |model: &ModelInfo| benchmarks.iter().find(|b| b.model_id == model.id);
+        let fast_enough = by_quality()
+            .find(|m| measured(m).is_some_and(|b| b.real_time_factor <= TARGET_REAL_TIME_FACTOR));
+        let fastest = benchmarks
+            .iter()
+            .min_by(|a, b| a.real_time_factor.total_cmp(&b.real_time_factor))
+            .map(|b| b.model_id.clone());
+        return fast_enough.map(|m| m.id.clone()).or(fastest);
+    }
+
+    by_quality()
+        .find(|m| fits_hardware(m, hardware))
+        .map(|m| m.id.clone())
+}
+
+fn fits_hardware(model: &ModelInfo, hardware: &HardwareInfo) -> bool {
+    let enough_memory = hardware.total_memory_mb == 0
+        || model.size_mb * MEMORY_HEADROOM <= hardware.total_memory_mb;
+    let enough_threads =
+        model.size_mb < LARGE_MODEL_MB || hardware.cpu_threads >= LARGE_MODEL_THREADS;
+    enough_memory && enough_threads
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(id: &str, size_mb: u64) -> ModelInfo {
+        ModelInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            filename: String::new(),
+            url: None,
+            size_mb,
+            is_downloaded: false,
+            is_downloading: false,
+            partial_size: 0,
+            is_directory: false,
+            engine_type: EngineType::Whisper,
+        }
+    }
+
+    fn benchmark(model_id: &str, real_time_factor: f32) -> ModelBenchmark {
+        ModelBenchmark {
+            model_id: model_id.to_string(),
+            load_ms: 500,
+            transcribe_ms: 1000,
+            clip_seconds: 10.0,
+            real_time_factor,
+        }
+    }
+
+    fn models() -> Vec<ModelInfo> {
+        vec![
+            model("small", 244),
+            model("medium", 491),
+            model("parakeet-tdt-0.6b-v3", 850),
+            model("large", 1080),
+        ]
+    }
+
+    #[test]
+    fn recommendation_follows_hardware_without_benchmarks() {
+        let workstation = HardwareInfo {
+            cpu_threads: 16,
+            total_memory_mb: 32768,
+        };
+        assert_eq!(
+            recommend_model(&models(), &workstation, &[]).as_deref(),
+            Some("parakeet-tdt-0.6b-v3")
+        );
+
+        let laptop = HardwareInfo {
+            cpu_threads: 4,
+            total_memory_mb: 8192,
+        };
+        assert_eq!(
+            recommend_model(&models(), &laptop, &[]).as_deref(),
+            Some("medium")
+        );
+
+        let tiny = HardwareInfo {
+            cpu_threads: 2,
+            total_memory_mb: 1024,
+        };
+        assert_eq!(
+            recommend_model(&models(), &tiny, &[]).as_deref(),
+            Some("small")
+        );
+    }
+
+    #[test]
+    fn recommendation_prefers_measured_speed() {
+        let hardware = HardwareInfo {
+            cpu_threads: 16,
+            total_memory_mb: 32768,
+        };
+        let benchmarks = [
+            benchmark("parakeet-tdt-0.6b-v3", 0.5),
+            benchmark("large", 0.8),
+            benchmark("small", 0.1),
+        ];
+        assert_eq!(
+            recommend_model(&models(), &hardware, &benchmarks).as_deref(),
+            Some("small")
+        );
+
+        // Nothing meets the target, so the fastest measured model wins
+        let slow = [benchmark("large", 0.9), benchmark("medium", 0.4)];
+        assert_eq!(
+            recommend_model(&models(), &hardware, &slow).as_deref(),
+            Some("medium")
+        );
+    }
+}
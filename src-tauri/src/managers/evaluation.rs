@@ -0,0 +1,332 @@
+use crate::audio_toolkit::{decode_audio_file, WordCorrection};
+use crate::managers::batch::collect_audio_files;
+use crate::managers::transcription::{
+    CancellationToken, Transcriber, TranscriptionManager, TranscriptionOptions,
+};
+use crate::settings::get_settings;
+use anyhow::{bail, Context, Result};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+use tauri::{AppHandle, Manager};
+
+/// Settings to evaluate. Anything left out uses the current settings, and
+/// the selected model when no model is given.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct EvaluationOptions {
+    #[serde(default)]
+    pub model_id: Option<String>,
+    #[serde(default)]
+    pub custom_words: Option<Vec<String>>,
+    #[serde(default)]
+    pub word_correction_threshold: Option<f64>,
+}
+
+/// Edit distance between a transcript and its reference, in words or
+/// characters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ErrorCounts {
+    /// Substitutions, insertions and deletions.
+    pub errors: usize,
+    pub reference_length: usize,
+}
+
+impl ErrorCounts {
+    pub fn rate(&self) -> f64 {
+        match self.reference_length {
+            0 if self.errors == 0 => 0.0,
+            0 => 1.0,
+            length => self.errors as f64 / length as f64,
+        }
+    }
+
+    fn add(&mut self, other: ErrorCounts) {
+        self.errors += other.errors;
+        self.reference_length += other.reference_length;
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct FileEvaluation {
+    pub file: String,
+    pub reference: String,
+    pub hypothesis: String,
+    pub words: ErrorCounts,
+    pub wer: f64,
+    pub characters: ErrorCounts,
+    pub cer: f64,
+    pub transcribe_ms: u64,
+    /// Replacements made by the custom word correction, with their scores.
+    pub corrections: Vec<WordCorrection>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct EvaluationFailure {
+    pub file: String,
+    pub error: String,
+}
+
+/// Result of an evaluation run, meant to be saved as JSON and diffed against
+/// other runs.
+#[derive(Clone, Debug, Serialize)]
+pub struct EvaluationReport {
+    pub model_id: Option<String>,
+    pub custom_words: Vec<String>,
+    pub word_correction_threshold: f64,
+    /// Corpus-wide rates: all errors over all reference words or characters.
+    pub wer: f64,
+    pub cer: f64,
+    pub words: ErrorCounts,
+    pub characters: ErrorCounts,
+    pub files: Vec<FileEvaluation>,
+    pub failures: Vec<EvaluationFailure>,
+}
+
+/// Transcribes every audio file in `directory` that has a reference
+/// transcript next to it (`clip.wav` and `clip.txt`) and scores the result.
+/// The model runs apart from dictation: the active model stays selected and
+/// loaded, and the evaluation fails if the model doesn't fit next to it in
+/// the memory budget.
+/// Blocks until every file was transcribed.
+pub fn evaluate_corpus(
+    app: &AppHandle,
+    directory: &Path,
+    options: &EvaluationOptions,
+) -> Result<EvaluationReport> {
+    let corpus: Vec<_> = collect_audio_files(directory)?
+        .into_iter()
+        .filter_map(|audio| {
+            let reference = audio.with_extension("txt");
+            reference.is_file().then_some((audio, reference))
+        })
+        .collect();
+    if corpus.is_empty() {
+        bail!(
+            "No audio files with a reference transcript found in {}",
+            directory.display()
+        );
+    }
+
+    let settings = get_settings(app);
+    let model_id = options
+        .model_id
+        .clone()
+        .unwrap_or_else(|| settings.selected_model.clone());
+    let transcription_options = TranscriptionOptions {
+        custom_words: Some(
+            options
+                .custom_words
+                .clone()
+                .unwrap_or(settings.custom_words),
+        ),
+        word_correction_threshold: Some(
+            options
+                .word_correction_threshold
+                .unwrap_or(settings.word_correction_threshold),
+        ),
+        standalone: true,
+        report_corrections: true,
+        ..Default::default()
+    };
+
+    let tm = app.state::<Arc<TranscriptionManager>>();
+    tm.with_model(&model_id, |transcribe| {
+        score_corpus(
+            &corpus,
+            transcribe,
+            Some(model_id.clone()),
+            &transcription_options,
+        )
+    })
+}
+
+/// Transcribes each `(audio, reference)` pair of `corpus` and adds up the
+/// errors. Files that fail are listed in the report instead.
+fn score_corpus(
+    corpus: &[(PathBuf, PathBuf)],
+    transcribe: &Transcriber,
+    model_id: Option<String>,
+    transcription_options: &TranscriptionOptions,
+) -> EvaluationReport {
+    let mut report = EvaluationReport {
+        model_id,
+        custom_words: transcription_options
+            .custom_words
+            .clone()
+            .unwrap_or_default(),
+        word_correction_threshold: transcription_options
+            .word_correction_threshold
+            .unwrap_or_default(),
+        wer: 0.0,
+        cer: 0.0,
+        words: ErrorCounts::default(),
+        characters: ErrorCounts::default(),
+        files: Vec::new(),
+        failures: Vec::new(),
+    };
+
+    for (audio, reference) in corpus {
+        let file = audio
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let evaluation = fs::read_to_string(reference)
+            .with_context(|| format!("Failed to read {}", reference.display()))
+            .and_then(|reference| {
+                let samples = decode_audio_file(audio)?;
+                let start = Instant::now();
+                let result = transcribe(
+                    samples,
+                    transcription_options,
+                    &CancellationToken::default(),
+                )?;
+                let transcribe_ms = start.elapsed().as_millis() as u64;
+                let mut evaluation =
+                    score_file(&file, reference.trim(), &result.text, transcribe_ms);
+                evaluation.corrections = result.word_corrections;
+                Ok(evaluation)
+            });
+
+        match evaluation {
+            Ok(evaluation) => {
+                debug!("{}: WER {:.3}", file, evaluation.wer);
+                report.words.add(evaluation.words);
+                report.characters.add(evaluation.characters);
+                report.files.push(evaluation);
+            }
+            Err(e) => report.failures.push(EvaluationFailure {
+                file,
+                error: format!("{:#}", e),
+            }),
+        }
+    }
+    report.wer = report.words.rate();
+    report.cer = report.characters.rate();
+    report
+}
+
+fn score_file(file: &str, reference: &str, hypothesis: &str, transcribe_ms: u64) -> FileEvaluation {
+    let words = word_errors(reference, hypothesis);
+    let characters = character_errors(reference, hypothesis);
+    FileEvaluation {
+        file: file.to_string(),
+        reference: reference.to_string(),
+        hypothesis: hypothesis.to_string(),
+        words,
+        wer: words.rate(),
+        characters,
+        cer: characters.rate(),
+        transcribe_ms,
+        corrections: Vec::new(),
+    }
+}
+
+/// Word-level edit distance, ignoring case and punctuation.
+pub fn word_errors(reference: &str, hypothesis: &str) -> ErrorCounts {
+    let reference = normalize_for_scoring(reference);
+    let hypothesis = normalize_for_scoring(hypothesis);
+    let reference: Vec<&str> = reference.split_whitespace().collect();
+    let hypothesis: Vec<&str> = hypothesis.split_whitespace().collect();
+    ErrorCounts {
+        errors: edit_distance(&reference, &hypothesis),
+        reference_length: reference.len(),
+    }
+}
+
+/// Character-level edit distance, ignoring case and punctuation. Words are
+/// separated by a single space, which counts as a character.
+pub fn character_errors(reference: &str, hypothesis: &str) -> ErrorCounts {
+    let reference: Vec<char> = normalize_for_scoring(reference).chars().collect();
+    let hypothesis: Vec<char> = normalize_for_scoring(hypothesis).chars().collect();
+    ErrorCounts {
+        errors: edit_distance(&reference, &hypothesis),
+        reference_length: reference.len(),
+    }
+}
+
+/// Lowercases and turns punctuation into spaces, keeping apostrophes within
+/// words ("don't").
+fn normalize_for_scoring(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '\'' {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .map(|word| word.trim_matches('\''))
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn edit_distance<T: PartialEq>(reference: &[T], hypothesis: &[T]) -> usize {
+    let mut previous: Vec<usize> = (0..=hypothesis.len()).collect();
+    let mut current = vec![0; hypothesis.len() + 1];
+    for (i, r) in reference.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, h) in hypothesis.iter().enumerate() {
+            let substitution = previous[j] + usize::from(r != h);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[hypothesis.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_errors_ignore_case_and_punctuation() {
+        let counts = word_errors("Send the report, please.", "send the report please");
+        assert_eq!(counts.errors, 0);
+        assert_eq!(counts.reference_length, 4);
+
+        // One substitution and one deletion
+        let counts = word_errors("Don't send the report", "don't sent report");
+        assert_eq!(
+            counts,
+            ErrorCounts {
+                errors: 2,
+                reference_length: 4
+            }
+        );
+        assert_eq!(counts.rate(), 0.5);
+
+        // Insertions can push the rate above one
+        assert_eq!(word_errors("yes", "yes yes yes").rate(), 2.0);
+    }
+
+    #[test]
+    fn character_errors_count_single_edits() {
+        let counts = character_errors("Kubernetes", "kubernetis");
+        assert_eq!(counts.errors, 1);
+        assert_eq!(counts.reference_length, 10);
+
+        assert_eq!(character_errors("a b", "ab").errors, 1);
+    }
+
+    #[test]
+    fn empty_reference_rates() {
+        assert_eq!(word_errors("", "").rate(), 0.0);
+        assert_eq!(word_errors("", "thank you").rate(), 1.0);
+        assert_eq!(word_errors("hello there", "").rate(), 1.0);
+    }
+
+    #[test]
+    fn aggregate_weights_by_reference_length() {
+        let mut total = ErrorCounts::default();
+        total.add(word_errors("one two three four", "one two three four"));
+        total.add(word_errors("five six", "five seven"));
+        assert_eq!(total.rate(), 1.0 / 6.0);
+    }
+}
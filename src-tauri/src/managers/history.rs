@@ -8,10 +8,21 @@ use std::path::{Path, PathBuf};
 use tauri::{App, AppHandle, Emitter, Manager};
 use tauri_plugin_sql::{Migration, MigrationKind};
 
-use crate::audio_toolkit::save_wav_file;
+use crate::audio_toolkit::{save_wav_file, Hallucination};
+use crate::managers::transcription::{TranscriptSegment, TranscriptionResult};
 
 const HISTORY_LIMIT: usize = 5;
 
+const CREATE_SEGMENTS_TABLE_SQL: &str = "CREATE TABLE IF NOT EXISTS transcription_segments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    history_id INTEGER NOT NULL,
+    segment_index INTEGER NOT NULL,
+    start_ms INTEGER NOT NULL,
+    end_ms INTEGER NOT NULL,
+    text TEXT NOT NULL,
+    words TEXT NOT NULL DEFAULT '[]'
+)";
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct HistoryEntry {
     pub id: i64,
@@ -20,6 +31,14 @@ pub struct HistoryEntry {
     pub saved: bool,
     pub title: String,
     pub transcription_text: String,
+    /// Language of the dictation, if it was known.
+    pub language: Option<String>,
+    /// Why the hallucination filter removed text from this dictation, if it
+    /// did. `transcription_text` is what remained.
+    pub filtered: Option<String>,
+    /// Name of the audio file this entry was transcribed from, if it was not
+    /// dictated.
+    pub source_file: Option<String>,
 }
 
 #[derive(Clone)]
@@ -72,6 +91,8 @@ impl HistoryManager {
         }]
     }
 
+    /// Schema changes after the first version are applied here rather than as
+    /// plugin migrations, which only run when the frontend opens the database.
     fn init_database(&self) -> Result<()> {
         let conn = Connection::open(&self.db_path)?;
         conn.execute(
@@ -85,6 +106,10 @@ impl HistoryManager {
             )",
             [],
         )?;
+        conn.execute(CREATE_SEGMENTS_TABLE_SQL, [])?;
+        ensure_column(&conn, "transcription_history", "language", "TEXT")?;
+        ensure_column(&conn, "transcription_history", "filtered", "TEXT")?;
+        ensure_column(&conn, "transcription_history", "source_file", "TEXT")?;
         debug!("Database initialized at: {:?}", self.db_path);
         Ok(())
     }
@@ -93,14 +118,17 @@ impl HistoryManager {
         Ok(Connection::open(&self.db_path)?)
     }
 
-    /// Save a transcription to history (both database and WAV file)
+    /// Save a transcription to history (both database and WAV file).
+    /// `source_file` names the audio file it was transcribed from, if any.
     pub async fn save_transcription(
         &self,
         audio_samples: Vec<f32>,
-        transcription_text: String,
+        transcription: TranscriptionResult,
+        source_file: Option<String>,
     ) -> Result<()> {
-        let timestamp = Utc::now().timestamp();
-        let file_name = format!("handy-{}.wav", timestamp);
+        let now = Utc::now();
+        let timestamp = now.timestamp();
+        let file_name = reserve_recording_file(&self.recordings_dir, now.timestamp_millis())?;
         let title = self.format_timestamp_title(timestamp);
 
         // Save WAV file
@@ -108,7 +136,7 @@ impl HistoryManager {
         save_wav_file(file_path, &audio_samples).await?;
 
         // Save to database
-        self.save_to_database(file_name, timestamp, title, transcription_text)?;
+        self.save_to_database(file_name, timestamp, title, transcription, source_file)?;
 
         // Clean up old entries
         self.cleanup_old_entries()?;
@@ -126,13 +154,24 @@ impl HistoryManager {
         file_name: String,
         timestamp: i64,
         title: String,
-        transcription_text: String,
+        transcription: TranscriptionResult,
+        source_file: Option<String>,
     ) -> Result<()> {
         let conn = self.get_connection()?;
         conn.execute(
-            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text) VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![file_name, timestamp, false, title, transcription_text],
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, language, filtered, source_file) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                file_name,
+                timestamp,
+                false,
+                title,
+                transcription.text,
+                transcription.language,
+                transcription.filtered.map(Hallucination::as_str),
+                source_file
+            ],
         )?;
+        insert_segments(&conn, conn.last_insert_rowid(), &transcription.segments)?;
 
         debug!("Saved transcription to database");
         Ok(())
@@ -164,6 +203,7 @@ impl HistoryManager {
                     "DELETE FROM transcription_history WHERE id = ?1",
                     params![id],
                 )?;
+                delete_segments(&conn, *id)?;
 
                 // Delete WAV file
                 let file_path = self.recordings_dir.join(file_name);
@@ -185,7 +225,7 @@ impl HistoryManager {
     pub async fn get_history_entries(&self) -> Result<Vec<HistoryEntry>> {
         let conn = self.get_connection()?;
         let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text FROM transcription_history ORDER BY timestamp DESC"
+            "SELECT id, file_name, timestamp, saved, title, transcription_text, language, filtered, source_file FROM transcription_history ORDER BY timestamp DESC"
         )?;
 
         let rows = stmt.query_map([], |row| {
@@ -196,6 +236,9 @@ impl HistoryManager {
                 saved: row.get("saved")?,
                 title: row.get("title")?,
                 transcription_text: row.get("transcription_text")?,
+                language: row.get("language")?,
+                filtered: row.get("filtered")?,
+                source_file: row.get("source_file")?,
             })
         })?;
 
@@ -207,6 +250,13 @@ impl HistoryManager {
         Ok(entries)
     }
 
+    /// Timed segments of a history entry, in order. Empty for entries recorded
+    /// before timestamps were stored.
+    pub async fn get_segments(&self, id: i64) -> Result<Vec<TranscriptSegment>> {
+        let conn = self.get_connection()?;
+        load_segments(&conn, id)
+    }
+
     pub async fn toggle_saved_status(&self, id: i64) -> Result<()> {
         let conn = self.get_connection()?;
 
@@ -245,7 +295,8 @@ impl HistoryManager {
     pub async fn get_entry_by_id(&self, id: i64) -> Result<Option<HistoryEntry>> {
         let conn = self.get_connection()?;
         let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text 
+            "SELECT id, file_name, timestamp, saved, title, transcription_text, language, filtered,
+                    source_file
              FROM transcription_history WHERE id = ?1",
         )?;
 
@@ -258,6 +309,9 @@ impl HistoryManager {
                     saved: row.get("saved")?,
                     title: row.get("title")?,
                     transcription_text: row.get("transcription_text")?,
+                    language: row.get("language")?,
+                    filtered: row.get("filtered")?,
+                    source_file: row.get("source_file")?,
                 })
             })
             .optional()?;
@@ -294,6 +348,7 @@ impl HistoryManager {
             "DELETE FROM transcription_history WHERE id = ?1",
             params![id],
         )?;
+        delete_segments(&conn, id)?;
 
         debug!("Deleted history entry with id: {}", id);
 
@@ -316,6 +371,85 @@ impl HistoryManager {
     }
 }
 
+/// Adds a column to an existing table unless it is already there, so databases
+/// created by older versions pick up new columns on startup.
+fn ensure_column(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>("name"))?
+        .filter_map(|name| name.ok())
+        .any(|name| name == column);
+
+    if !exists {
+        conn.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )?;
+        debug!("Added column {}.{}", table, column);
+    }
+    Ok(())
+}
+
+fn insert_segments(
+    conn: &Connection,
+    history_id: i64,
+    segments: &[TranscriptSegment],
+) -> Result<()> {
+    let mut stmt = conn.prepare(
+        "INSERT INTO transcription_segments (history_id, segment_index, start_ms, end_ms, text, words) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+
+    for (index, segment) in segments.iter().enumerate() {
+        stmt.execute(params![
+            history_id,
+            index as i64,
+            (segment.start * 1000.0).round() as i64,
+            (segment.end * 1000.0).round() as i64,
+            segment.text,
+            serde_json::to_string(&segment.words)?,
+        ])?;
+    }
+
+    Ok(())
+}
+
+fn load_segments(conn: &Connection, history_id: i64) -> Result<Vec<TranscriptSegment>> {
+    let mut stmt = conn.prepare(
+        "SELECT start_ms, end_ms, text, words FROM transcription_segments WHERE history_id = ?1 ORDER BY segment_index",
+    )?;
+
+    let rows = stmt.query_map([history_id], |row| {
+        Ok((
+            row.get::<_, i64>("start_ms")?,
+            row.get::<_, i64>("end_ms")?,
+            row.get::<_, String>("text")?,
+            row.get::<_, String>("words")?,
+        ))
+    })?;
+
+    let mut segments = Vec::new();
+    for row in rows {
+        let (start_ms, end_ms, text, words) = row?;
+        segments.push(TranscriptSegment {
+            start: start_ms as f32 / 1000.0,
+            end: end_ms as f32 / 1000.0,
+            text,
+            words: serde_json::from_str(&words)
+                .with_context(|| format!("invalid word timings for entry {}", history_id))?,
+        });
+    }
+
+    Ok(segments)
+}
+
+fn delete_segments(conn: &Connection, history_id: i64) -> Result<()> {
+    conn.execute(
+        "DELETE FROM transcription_segments WHERE history_id = ?1",
+        params![history_id],
+    )?;
+    Ok(())
+}
+
 fn sanitize_history_path(recordings_dir: &Path, file_name: &str) -> Result<PathBuf> {
     // Ensure the filename is just a filename, not a path.
     if Path::new(file_name).components().count() != 1 {
@@ -336,6 +470,30 @@ fn sanitize_history_path(recordings_dir: &Path, file_name: &str) -> Result<PathB
     Ok(candidate)
 }
 
+/// Claims a new recording file name in `recordings_dir` for a save at
+/// `timestamp_ms`. The file is created empty right away, so saves within the
+/// same millisecond, even from another process, get different names.
+fn reserve_recording_file(recordings_dir: &Path, timestamp_ms: i64) -> Result<String> {
+    let mut attempt = 0;
+    loop {
+        let file_name = match attempt {
+            0 => format!("handy-{}.wav", timestamp_ms),
+            n => format!("handy-{}-{}.wav", timestamp_ms, n),
+        };
+        let created = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(recordings_dir.join(&file_name));
+        match created {
+            Ok(_) => return Ok(file_name),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => attempt += 1,
+            Err(e) => {
+                return Err(e).with_context(|| format!("failed to create {}", file_name));
+            }
+        }
+    }
+}
+
 fn read_history_audio_from(recordings_dir: &Path, file_name: &str) -> Result<Vec<u8>> {
     let path = sanitize_history_path(recordings_dir, file_name)?;
 
@@ -369,6 +527,18 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn reserve_recording_file_never_reuses_a_name() -> Result<()> {
+        let dir = tempdir()?;
+        let first = reserve_recording_file(dir.path(), 1_700_000_000_123)?;
+        let second = reserve_recording_file(dir.path(), 1_700_000_000_123)?;
+
+        assert_eq!(first, "handy-1700000000123.wav");
+        assert_eq!(second, "handy-1700000000123-1.wav");
+        assert!(dir.path().join(&first).exists() && dir.path().join(&second).exists());
+        Ok(())
+    }
+
     #[test]
     fn read_history_audio_from_returns_bytes() -> Result<()> {
         let dir = tempdir()?;
@@ -382,6 +552,48 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn segments_round_trip_through_database() -> Result<()> {
+        use crate::managers::transcription::TranscriptWord;
+
+        let conn = Connection::open_in_memory()?;
+        conn.execute(CREATE_SEGMENTS_TABLE_SQL, [])?;
+        let segments = vec![
+            TranscriptSegment {
+                start: 0.0,
+                end: 1.25,
+                text: "Hello there.".to_string(),
+                words: vec![
+                    TranscriptWord {
+                        start: 0.0,
+                        end: 0.5,
+                        text: "Hello".to_string(),
+                    },
+                    TranscriptWord {
+                        start: 0.6,
+                        end: 1.25,
+                        text: "there.".to_string(),
+                    },
+                ],
+            },
+            TranscriptSegment {
+                start: 1.5,
+                end: 2.0,
+                text: "Bye.".to_string(),
+                words: Vec::new(),
+            },
+        ];
+
+        insert_segments(&conn, 7, &segments)?;
+
+        assert_eq!(load_segments(&conn, 7)?, segments);
+        assert!(load_segments(&conn, 8)?.is_empty());
+
+        delete_segments(&conn, 7)?;
+        assert!(load_segments(&conn, 7)?.is_empty());
+        Ok(())
+    }
+
     #[test]
     fn read_history_audio_from_missing_file_errors() {
         let dir = tempdir().expect("temp dir");
@@ -389,4 +601,27 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn ensure_column_adds_missing_column_once() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+        conn.execute(
+            "CREATE TABLE transcription_history (id INTEGER PRIMARY KEY, transcription_text TEXT NOT NULL)",
+            [],
+        )?;
+
+        ensure_column(&conn, "transcription_history", "language", "TEXT")?;
+        ensure_column(&conn, "transcription_history", "language", "TEXT")?;
+
+        conn.execute(
+            "INSERT INTO transcription_history (transcription_text, language) VALUES ('hallo', 'de')",
+            [],
+        )?;
+        let language: Option<String> =
+            conn.query_row("SELECT language FROM transcription_history", [], |row| {
+                row.get(0)
+            })?;
+        assert_eq!(language.as_deref(), Some("de"));
+        Ok(())
+    }
 }
@@ -1,4 +1,8 @@
 pub mod audio;
+pub mod batch;
+pub mod benchmark;
+pub mod evaluation;
 pub mod history;
 pub mod model;
+pub mod queue;
 pub mod transcription;
@@ -20,11 +20,14 @@ const MANIFEST_RESOURCE_PATH: &str = "resources/models/manifest.json";
 const MODEL_DOWNLOAD_USER_AGENT: &str = "HandyModelManager/1.0 (+https://handy.computer)";
 const MODEL_DOWNLOAD_TIMEOUT_SECS: u64 = 600;
 const MODEL_CONNECT_TIMEOUT_SECS: u64 = 30;
+pub const REMOTE_MODEL_ID: &str = "remote";
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EngineType {
     Whisper,
     Parakeet,
+    /// OpenAI-compatible transcription server configured in the settings.
+    Remote,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -355,6 +358,24 @@ impl ModelManager {
             },
         );
 
+        // Remote transcription server, available once an endpoint is configured
+        available_models.insert(
+            REMOTE_MODEL_ID.to_string(),
+            ModelInfo {
+                id: REMOTE_MODEL_ID.to_string(),
+                name: "Remote Server".to_string(),
+                description: "Transcribes on an OpenAI-compatible server.".to_string(),
+                filename: String::new(),
+                url: None,
+                size_mb: 0,
+                is_downloaded: false,
+                is_downloading: false,
+                partial_size: 0,
+                is_directory: false,
+                engine_type: EngineType::Remote,
+            },
+        );
+
         let manifest = ModelManifest::load(&app_handle)?;
 
         let manager = Self {
@@ -413,11 +434,19 @@ impl ModelManager {
         Ok(())
     }
 
-    fn update_download_status(&self) -> Result<()> {
+    pub fn update_download_status(&self) -> Result<()> {
+        let remote_configured = !get_settings(&self.app_handle)
+            .remote_engine
+            .endpoint
+            .trim()
+            .is_empty();
         let mut models = self.available_models.lock().unwrap();
 
         for model in models.values_mut() {
-            if model.is_directory {
+            if model.engine_type == EngineType::Remote {
+                // Nothing to download, the model is usable once configured
+                model.is_downloaded = remote_configured;
+            } else if model.is_directory {
                 // For directory-based models, check if the directory exists
                 let model_path = self.models_dir.join(&model.filename);
                 let partial_path = self.models_dir.join(format!("{}.partial", &model.filename));
@@ -495,6 +524,12 @@ impl ModelManager {
         let model_info =
             model_info.ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
 
+        if model_info.engine_type == EngineType::Remote {
+            return Err(anyhow::anyhow!(
+                "Remote models are configured in the settings, not downloaded"
+            ));
+        }
+
         let digest = self
             .manifest
             .digest_for(&model_info.id)
@@ -776,6 +811,12 @@ impl ModelManager {
 
         println!("ModelManager: Found model info: {:?}", model_info);
 
+        if model_info.engine_type == EngineType::Remote {
+            return Err(anyhow::anyhow!(
+                "Remote models have no local files to delete"
+            ));
+        }
+
         let model_path = self.models_dir.join(&model_info.filename);
         let partial_path = self
             .models_dir
@@ -834,6 +875,11 @@ impl ModelManager {
             return Err(anyhow::anyhow!("Model not available: {}", model_id));
         }
 
+        // Remote models have no files on disk
+        if model_info.engine_type == EngineType::Remote {
+            return Ok(PathBuf::new());
+        }
+
         // Ensure we don't return partial files/directories
         if model_info.is_downloading {
             return Err(anyhow::anyhow!(
@@ -0,0 +1,242 @@
+use crate::managers::audio::AudioRecordingManager;
+use crate::managers::history::HistoryManager;
+use crate::managers::transcription::{
+    CancellationToken, PendingDictation, TranscriptionCancelled, TranscriptionManager,
+};
+use crate::tray::{change_tray_icon, TrayIconState};
+use crate::utils;
+use log::{debug, error};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+use tauri::{App, AppHandle, Emitter, Manager};
+
+/// A stopped recording waiting to be transcribed.
+struct TranscriptionJob {
+    id: u64,
+    samples: Vec<f32>,
+    dictation: PendingDictation,
+    /// Checked again before the text is saved or pasted, since a cancel can
+    /// arrive after the transcription itself finished.
+    cancel: CancellationToken,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct QueueStatusEvent {
+    /// Jobs that have not finished yet, including the one being transcribed.
+    pub depth: usize,
+    /// Job currently being transcribed, if any.
+    pub active_job: Option<u64>,
+}
+
+/// Transcribes stopped recordings one after another on a dedicated thread, so
+/// a new recording can start right away. Jobs run in the order they were
+/// queued and their text is pasted in that same order.
+pub struct TranscriptionQueue {
+    app_handle: AppHandle,
+    job_tx: Mutex<Option<mpsc::Sender<TranscriptionJob>>>,
+    worker: Mutex<Option<thread::JoinHandle<()>>>,
+    depth: Arc<AtomicUsize>,
+    next_id: AtomicU64,
+    /// Cancellation tokens of the jobs that have not finished yet.
+    pending: Arc<Mutex<HashMap<u64, CancellationToken>>>,
+}
+
+impl TranscriptionQueue {
+    pub fn new(app: &App) -> Self {
+        let app_handle = app.app_handle().clone();
+        let depth = Arc::new(AtomicUsize::new(0));
+        let pending = Arc::new(Mutex::new(HashMap::<u64, CancellationToken>::new()));
+        let (job_tx, job_rx) = mpsc::channel::<TranscriptionJob>();
+
+        let worker = {
+            let app_handle = app_handle.clone();
+            let depth = depth.clone();
+            let pending = pending.clone();
+            thread::spawn(move || {
+                // Ends once the queue is dropped and the sender with it
+                while let Ok(job) = job_rx.recv() {
+                    let id = job.id;
+                    emit_status(&app_handle, depth.load(Ordering::SeqCst), Some(id));
+                    process_job(&app_handle, job);
+                    pending.lock().unwrap().remove(&id);
+
+                    let remaining = depth.fetch_sub(1, Ordering::SeqCst) - 1;
+                    emit_status(&app_handle, remaining, None);
+                    if remaining == 0 {
+                        finish_idle(&app_handle);
+                    }
+                }
+                debug!("Transcription queue worker stopped");
+            })
+        };
+
+        Self {
+            app_handle,
+            job_tx: Mutex::new(Some(job_tx)),
+            worker: Mutex::new(Some(worker)),
+            depth,
+            next_id: AtomicU64::new(1),
+            pending,
+        }
+    }
+
+    /// Queues a stopped recording for transcription and returns its job id.
+    pub fn enqueue(&self, samples: Vec<f32>, dictation: PendingDictation) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let depth = self.depth.fetch_add(1, Ordering::SeqCst) + 1;
+        debug!("Queued transcription job {} (depth {})", id, depth);
+
+        let cancel = dictation.cancel_token();
+        self.pending.lock().unwrap().insert(id, cancel.clone());
+        let job = TranscriptionJob {
+            id,
+            samples,
+            dictation,
+            cancel,
+        };
+        let sent = match self.job_tx.lock().unwrap().as_ref() {
+            Some(job_tx) => job_tx.send(job).is_ok(),
+            None => false,
+        };
+        if sent {
+            emit_status(&self.app_handle, depth, None);
+        } else {
+            error!("Transcription queue is shut down, dropping job {}", id);
+            self.pending.lock().unwrap().remove(&id);
+            self.depth.fetch_sub(1, Ordering::SeqCst);
+        }
+        id
+    }
+
+    /// Number of queued jobs that have not finished yet.
+    pub fn depth(&self) -> usize {
+        self.depth.load(Ordering::SeqCst)
+    }
+
+    /// Cancels every job that has not finished yet, whether it is waiting or
+    /// being transcribed. None of them is saved or pasted.
+    pub fn cancel_all(&self) {
+        let pending = self.pending.lock().unwrap();
+        for cancel in pending.values() {
+            cancel.cancel();
+        }
+        if !pending.is_empty() {
+            debug!("Cancelled {} queued transcription jobs", pending.len());
+        }
+    }
+}
+
+impl Drop for TranscriptionQueue {
+    fn drop(&mut self) {
+        debug!("Shutting down TranscriptionQueue");
+
+        // Closing the channel lets the worker finish queued jobs and exit
+        self.job_tx.lock().unwrap().take();
+        if let Some(handle) = self.worker.lock().unwrap().take() {
+            if let Err(e) = handle.join() {
+                eprintln!("Failed to join transcription queue worker: {:?}", e);
+            }
+        }
+    }
+}
+
+fn emit_status(app_handle: &AppHandle, depth: usize, active_job: Option<u64>) {
+    let _ = app_handle.emit(
+        "transcription-queue",
+        QueueStatusEvent { depth, active_job },
+    );
+}
+
+fn process_job(app_handle: &AppHandle, job: TranscriptionJob) {
+    let tm = app_handle.state::<Arc<TranscriptionManager>>();
+    let hm = Arc::clone(&app_handle.state::<Arc<HistoryManager>>());
+
+    let transcription_time = Instant::now();
+    let samples_clone = job.samples.clone(); // Clone for history saving
+    let transcription = match tm.complete_stream(job.dictation, job.samples) {
+        Ok(transcription) => transcription,
+        Err(err) if err.is::<TranscriptionCancelled>() => {
+            debug!("Job {} cancelled, discarding result", job.id);
+            return;
+        }
+        Err(err) => {
+            debug!("Job {} transcription error: {}", job.id, err);
+            return;
+        }
+    };
+    debug!(
+        "Job {} transcribed in {:?}: '{}'",
+        job.id,
+        transcription_time.elapsed(),
+        transcription.text
+    );
+
+    // Fully filtered dictations still go to history, so they can be reviewed
+    if transcription.text.is_empty() && transcription.filtered.is_none() {
+        return;
+    }
+    if job.cancel.is_cancelled() {
+        debug!(
+            "Job {} cancelled after transcription, discarding result",
+            job.id
+        );
+        return;
+    }
+
+    let transcription_text = transcription.text.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = hm
+            .save_transcription(samples_clone, transcription, None)
+            .await
+        {
+            error!("Failed to save transcription to history: {}", e);
+        }
+    });
+    if transcription_text.is_empty() {
+        return;
+    }
+
+    // The main thread runs these in submission order, which keeps pastes in
+    // the order the recordings were made
+    let ah = app_handle.clone();
+    let paste_time = Instant::now();
+    let cancel = job.cancel;
+    app_handle
+        .run_on_main_thread(move || {
+            // Pastes queued on the main thread can still be cancelled
+            if cancel.is_cancelled() {
+                debug!("Job {} cancelled before pasting", job.id);
+                return;
+            }
+            match utils::paste(transcription_text, ah) {
+                Ok(()) => debug!("Text pasted successfully in {:?}", paste_time.elapsed()),
+                Err(e) => eprintln!("Failed to paste transcription: {}", e),
+            }
+        })
+        .unwrap_or_else(|e| eprintln!("Failed to run paste on main thread: {:?}", e));
+}
+
+/// Returns the overlay and tray to idle once the last job is done, unless a
+/// new recording is already in progress.
+fn finish_idle(app_handle: &AppHandle) {
+    let rm = app_handle.state::<Arc<AudioRecordingManager>>();
+    if rm.is_recording() {
+        return;
+    }
+
+    let ah = app_handle.clone();
+    app_handle
+        .run_on_main_thread(move || {
+            utils::hide_recording_overlay(&ah);
+            change_tray_icon(&ah, TrayIconState::Idle);
+        })
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to reset overlay on main thread: {:?}", e);
+            utils::hide_recording_overlay(app_handle);
+            change_tray_icon(app_handle, TrayIconState::Idle);
+        });
+}
@@ -1,23 +1,24 @@
-use crate::audio_toolkit::apply_custom_words;
+use crate::audio_toolkit::audio::split_at_silence;
+use crate::audio_toolkit::{
+    append_deduplicated, apply_spoken_punctuation, build_initial_prompt, constants,
+    correct_custom_words, detect_language, filter_hallucinations, protected_word_set,
+    CompiledRules, Hallucination, WordCorrection,
+};
+use crate::engines::{
+    EngineCapabilities, EngineRegistry, InferenceRequest, ParakeetSpeechEngine, RemoteSpeechEngine,
+    SpeechEngine,
+};
 use crate::managers::model::{EngineType, ModelManager};
-use crate::settings::{get_settings, ModelUnloadTimeout};
+use crate::settings::{get_settings, AppSettings, ModelInferenceSettings, ModelUnloadTimeout};
 use anyhow::Result;
-use log::debug;
-use serde::Serialize;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, Mutex};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Condvar, Mutex};
 use std::thread;
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, Instant};
 use tauri::{App, AppHandle, Emitter, Manager};
-use transcribe_rs::{
-    engines::{
-        parakeet::{
-            ParakeetEngine, ParakeetInferenceParams, ParakeetModelParams, TimestampGranularity,
-        },
-        whisper::{WhisperEngine, WhisperInferenceParams},
-    },
-    TranscriptionEngine,
-};
 
 #[derive(Clone, Debug, Serialize)]
 pub struct ModelStateEvent {
@@ -27,19 +28,814 @@ pub struct ModelStateEvent {
     pub error: Option<String>,
 }
 
-enum LoadedEngine {
-    Whisper(WhisperEngine),
-    Parakeet(ParakeetEngine),
+/// Upper bound on the number of words de-duplicated between overlapping chunks.
+const MAX_OVERLAP_WORDS: usize = 16;
+/// Whisper keeps at most 224 prompt tokens; stay well below that in characters.
+const MAX_INITIAL_PROMPT_CHARS: usize = 600;
+
+/// Per-request overrides of the transcription settings.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct TranscriptionOptions {
+    /// Translate the speech to English. Falls back to `translate_to_english`
+    /// when not set.
+    #[serde(default)]
+    pub translate: Option<bool>,
+    /// Language spoken in the audio. Falls back to `selected_language`, or to
+    /// the only auto-detection candidate when exactly one is configured.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Words to correct the transcript towards. Falls back to `custom_words`.
+    #[serde(default)]
+    pub custom_words: Option<Vec<String>>,
+    /// Falls back to `word_correction_threshold`.
+    #[serde(default)]
+    pub word_correction_threshold: Option<f64>,
+    /// The audio is not a live dictation, e.g. a file. It neither uses nor
+    /// updates the context carried over between dictations.
+    #[serde(skip)]
+    pub standalone: bool,
+    /// Fill in `word_corrections` of the result even outside debug mode.
+    #[serde(skip)]
+    pub report_corrections: bool,
+}
+
+impl TranscriptionOptions {
+    fn translate(&self, settings: &AppSettings) -> bool {
+        self.translate.unwrap_or(settings.translate_to_english)
+    }
+
+    fn custom_words<'a>(&'a self, settings: &'a AppSettings) -> &'a [String] {
+        self.custom_words
+            .as_deref()
+            .unwrap_or(&settings.custom_words)
+    }
+
+    fn word_correction_threshold(&self, settings: &AppSettings) -> f64 {
+        self.word_correction_threshold
+            .unwrap_or(settings.word_correction_threshold)
+    }
+
+    /// The language to force on the engine, or `None` to auto-detect.
+    fn language(&self, settings: &AppSettings) -> Option<String> {
+        if let Some(language) = &self.language {
+            return Some(language.clone());
+        }
+        if settings.selected_language != "auto" {
+            return Some(settings.selected_language.clone());
+        }
+        match settings.language_candidates.as_slice() {
+            [only] => Some(only.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Returned, wrapped in `anyhow::Error`, when a transcription is cancelled.
+#[derive(Debug)]
+pub struct TranscriptionCancelled;
+
+impl std::fmt::Display for TranscriptionCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Transcription was cancelled")
+    }
+}
+
+impl std::error::Error for TranscriptionCancelled {}
+
+/// Shared flag that stops a transcription job.
+///
+/// The flag is checked before every chunk and handed to the engine, which
+/// stops mid-chunk where it can: Whisper through its abort callback, the
+/// remote engine by dropping the request in flight. Parakeet finishes the
+/// running chunk first; `max_chunk_seconds` bounds how long that takes.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<CancelState>);
+
+#[derive(Debug, Default)]
+struct CancelState {
+    cancelled: AtomicBool,
+    notify: tokio::sync::Notify,
+}
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::Relaxed);
+        self.0.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Completes once the token is cancelled, for racing async work against
+    /// it.
+    pub async fn cancelled(&self) {
+        // Registered before the check, so a cancel in between is not missed
+        let notified = self.0.notify.notified();
+        if self.is_cancelled() {
+            return;
+        }
+        notified.await;
+    }
+
+    pub(crate) fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            Err(TranscriptionCancelled.into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TranscriptionPartialEvent {
+    pub text: String,
+}
+
+/// A single word with its timing. Times are in seconds from the start of the
+/// recording.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptWord {
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+}
+
+/// A sentence-like span of the transcript. `words` is only filled in when the
+/// engine reports word-level timing.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+    #[serde(default)]
+    pub words: Vec<TranscriptWord>,
+}
+
+impl TranscriptSegment {
+    fn shift(&mut self, offset: f32) {
+        self.start += offset;
+        self.end += offset;
+        for word in &mut self.words {
+            word.start += offset;
+            word.end += offset;
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct TranscriptionResult {
+    pub text: String,
+    pub segments: Vec<TranscriptSegment>,
+    /// Language of the speech, either forced by the settings or detected.
+    /// `None` when it could not be determined.
+    pub language: Option<String>,
+    /// Set when the hallucination filter removed some or all of the text.
+    pub filtered: Option<Hallucination>,
+    /// Replacements made by the custom word correction, only filled in debug
+    /// mode.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub word_corrections: Vec<WordCorrection>,
+}
+
+impl TranscriptionResult {
+    /// Appends a result whose audio starts `offset` seconds into the recording.
+    /// A non-zero `max_overlap_words` means both results cover some of the same
+    /// audio, so repeated words and segments are dropped.
+    fn append(&mut self, other: TranscriptionResult, offset: f32, max_overlap_words: usize) {
+        self.text = append_deduplicated(&self.text, &other.text, max_overlap_words);
+        if self.language.is_none() {
+            self.language = other.language;
+        }
+
+        let last_end = self.segments.last().map_or(0.0, |s| s.end);
+        for mut segment in other.segments {
+            segment.shift(offset);
+            if max_overlap_words > 0 && (segment.start + segment.end) / 2.0 < last_end {
+                continue;
+            }
+            self.segments.push(segment);
+        }
+    }
+}
+
+/// Groups word timings into segments, closing a segment after every word that
+/// ends a sentence.
+pub(crate) fn group_words_into_segments(words: Vec<TranscriptWord>) -> Vec<TranscriptSegment> {
+    let mut segments = Vec::new();
+    let mut current: Vec<TranscriptWord> = Vec::new();
+
+    for word in words {
+        let ends_sentence = word.text.ends_with(['.', '?', '!']);
+        current.push(word);
+        if ends_sentence {
+            segments.push(segment_from_words(std::mem::take(&mut current)));
+        }
+    }
+    if !current.is_empty() {
+        segments.push(segment_from_words(current));
+    }
+
+    segments
+}
+
+fn segment_from_words(words: Vec<TranscriptWord>) -> TranscriptSegment {
+    TranscriptSegment {
+        start: words.first().map_or(0.0, |w| w.start),
+        end: words.last().map_or(0.0, |w| w.end),
+        text: words
+            .iter()
+            .map(|w| w.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        words,
+    }
+}
+
+/// Inference settings stored for `model_id`. Settings the engine does not
+/// accept, e.g. edited by hand, fall back to the engine defaults.
+fn inference_params(
+    model_id: &str,
+    settings: &AppSettings,
+    capabilities: &EngineCapabilities,
+) -> ModelInferenceSettings {
+    match settings.model_inference.get(model_id) {
+        Some(params) => match capabilities.validate(params) {
+            Ok(()) => params.clone(),
+            Err(e) => {
+                warn!("Ignoring inference settings of {}: {}", model_id, e);
+                ModelInferenceSettings::default()
+            }
+        },
+        None => ModelInferenceSettings::default(),
+    }
+}
+
+/// Runs `engine` on `audio` and returns its raw output, without any
+/// post-processing. Long buffers are split at silence gaps into windows of at
+/// most `max_chunk_seconds` and the results stitched back together.
+fn transcribe_chunks(
+    engine: &Mutex<Box<dyn SpeechEngine>>,
+    audio: &[f32],
+    model_id: &str,
+    settings: &AppSettings,
+    options: &TranscriptionOptions,
+    carried_context: &str,
+    cancel: &CancellationToken,
+) -> Result<TranscriptionResult> {
+    // Settle the language once, so chunks can't disagree on it
+    let detected =
+        detect_candidate_language(&mut **engine.lock().unwrap(), audio, settings, options)?;
+    let options = match detected {
+        Some(language) => TranscriptionOptions {
+            language: Some(language),
+            ..options.clone()
+        },
+        None => options.clone(),
+    };
+
+    let max_samples = settings.max_chunk_seconds as usize * constants::WHISPER_SAMPLE_RATE as usize;
+    let chunks = split_at_silence(audio, max_samples);
+    if chunks.len() > 1 {
+        debug!(
+            "Transcribing {} samples in {} chunks",
+            audio.len(),
+            chunks.len()
+        );
+    }
+
+    let mut result = TranscriptionResult::default();
+    for chunk in chunks {
+        cancel.check()?;
+        let offset = chunk.range.start as f32 / constants::WHISPER_SAMPLE_RATE as f32;
+        let part = run_engine(
+            &mut **engine.lock().unwrap(),
+            audio[chunk.range].to_vec(),
+            model_id,
+            settings,
+            &options,
+            carried_context,
+            cancel,
+        )?;
+        let max_overlap = if chunk.overlaps_previous {
+            MAX_OVERLAP_WORDS
+        } else {
+            0
+        };
+        result.append(part, offset, max_overlap);
+    }
+
+    // The last chunk may have finished after a cancel request
+    cancel.check()?;
+    if result.language.is_none() {
+        result.language = options.language(settings);
+    }
+    Ok(result)
+}
+
+/// With several language candidates and nothing forced, asks the engine which
+/// candidate `audio` is most likely spoken in. `None` when there is nothing to
+/// choose or the engine can't tell.
+fn detect_candidate_language(
+    engine: &mut dyn SpeechEngine,
+    audio: &[f32],
+    settings: &AppSettings,
+    options: &TranscriptionOptions,
+) -> Result<Option<String>> {
+    let candidates = &settings.language_candidates;
+    // A translation is English no matter what was spoken
+    if candidates.len() < 2
+        || options.language(settings).is_some()
+        || options.translate(settings)
+        || !engine.capabilities().language_selection
+    {
+        return Ok(None);
+    }
+
+    let detected = engine.detect_language(audio, candidates)?;
+    debug!("Most likely language candidate: {:?}", detected);
+    Ok(detected)
+}
+
+fn run_engine(
+    engine: &mut dyn SpeechEngine,
+    audio: Vec<f32>,
+    model_id: &str,
+    settings: &AppSettings,
+    options: &TranscriptionOptions,
+    carried_context: &str,
+    cancel: &CancellationToken,
+) -> Result<TranscriptionResult> {
+    let translate = options.translate(settings);
+
+    let capabilities = engine.capabilities();
+    if translate && !capabilities.translation {
+        return Err(anyhow::anyhow!(
+            "Translation to English is not supported by {} models. Turn off \"Translate to English\" or select a Whisper model.",
+            engine.name()
+        ));
+    }
+
+    let request = InferenceRequest {
+        language: if capabilities.language_selection {
+            options.language(settings)
+        } else {
+            None
+        },
+        translate,
+        initial_prompt: if capabilities.initial_prompt {
+            build_initial_prompt(
+                options.custom_words(settings),
+                &settings.prompt_context,
+                carried_context,
+                MAX_INITIAL_PROMPT_CHARS,
+            )
+        } else {
+            None
+        },
+        params: inference_params(model_id, settings, &capabilities),
+    };
+
+    engine.transcribe(audio, &request, cancel)
+}
+
+/// Fills in the language of a raw result the engine did not report, guessing
+/// from the text among the configured candidates. Never runs the engine again.
+fn settle_language(
+    mut result: TranscriptionResult,
+    settings: &AppSettings,
+    options: &TranscriptionOptions,
+) -> TranscriptionResult {
+    if let Some(language) = options.language(settings) {
+        result.language = Some(language);
+        return result;
+    }
+    // A translation is English no matter what was spoken
+    if options.translate(settings) || result.text.trim().is_empty() {
+        return result;
+    }
+    if result.language.is_none() {
+        result.language = detect_language(&result.text, &settings.language_candidates);
+    }
+    result
+}
+
+/// Settles the language of raw engine output, filters hallucinations and
+/// applies user corrections once a dictation of `speech_samples` samples is
+/// complete.
+fn post_process(
+    result: TranscriptionResult,
+    speech_samples: usize,
+    settings: &AppSettings,
+    options: &TranscriptionOptions,
+) -> TranscriptionResult {
+    let mut result = settle_language(result, settings, options);
+
+    let speech_seconds = speech_samples as f32 / constants::WHISPER_SAMPLE_RATE as f32;
+    let (text, filtered) = filter_hallucinations(&result.text, speech_seconds);
+    if let Some(reason) = filtered {
+        debug!(
+            "Hallucination filter ({}) turned '{}' into '{}'",
+            reason.as_str(),
+            result.text,
+            text
+        );
+        result.text = text;
+        result.filtered = filtered;
+        if result.text.is_empty() {
+            result.segments.clear();
+        } else {
+            for segment in &mut result.segments {
+                segment.text = filter_hallucinations(&segment.text, speech_seconds).0;
+            }
+            result.segments.retain(|s| !s.text.is_empty());
+        }
+    }
+
+    let language = result
+        .language
+        .clone()
+        .or_else(|| options.language(settings))
+        .unwrap_or_else(|| "en".to_string());
+
+    // Apply word correction if custom words are configured
+    let custom_words = options.custom_words(settings);
+    if !custom_words.is_empty() {
+        let threshold = options.word_correction_threshold(settings);
+        let protected = protected_word_set(
+            &settings.protected_words,
+            custom_words,
+            settings.protect_frequent_words.then_some(language.as_str()),
+        );
+        let (text, corrections) =
+            correct_custom_words(&result.text, custom_words, threshold, &protected);
+        result.text = text;
+        for segment in &mut result.segments {
+            segment.text =
+                correct_custom_words(&segment.text, custom_words, threshold, &protected).0;
+        }
+
+        if settings.debug_mode || options.report_corrections {
+            for correction in &corrections {
+                info!(
+                    "Word correction: '{}' -> '{}' (score {:.3}, threshold {:.3})",
+                    correction.original, correction.replacement, correction.score, threshold
+                );
+            }
+            result.word_corrections = corrections;
+        }
+    }
+
+    // Explicit rules go last, so fuzzy matching cannot undo them
+    if !settings.replacement_rules.is_empty() {
+        let rules = CompiledRules::new(&settings.replacement_rules);
+        result.text = rules.apply(&result.text);
+        for segment in &mut result.segments {
+            segment.text = rules.apply(&segment.text);
+        }
+    }
+
+    result.text = result.text.trim().to_string();
+
+    // After trimming, so a trailing "new line" survives
+    if settings.spoken_punctuation.enabled {
+        let primary = language.split(['-', '_']).next().unwrap_or(&language);
+        let no_custom = HashMap::new();
+        let custom = settings
+            .spoken_punctuation
+            .custom_phrases
+            .get(primary)
+            .unwrap_or(&no_custom);
+        let convert = |text: &str| apply_spoken_punctuation(text, &language, custom);
+        result.text = convert(&result.text);
+        for segment in &mut result.segments {
+            segment.text = convert(&segment.text);
+        }
+    }
+
+    result
+}
+
+/// Lets only one model load run at a time. Callers arriving while a load is
+/// running wait for it to finish before running their own closure.
+#[derive(Default)]
+struct LoadGate {
+    busy: Mutex<bool>,
+    finished: Condvar,
+}
+
+impl LoadGate {
+    fn run<T>(&self, load: impl FnOnce() -> T) -> T {
+        /// Reopens the gate even if `load` panics.
+        struct Release<'a>(&'a LoadGate);
+
+        impl Drop for Release<'_> {
+            fn drop(&mut self) {
+                *self.0.busy.lock().unwrap() = false;
+                self.0.finished.notify_all();
+            }
+        }
+
+        let mut busy = self.busy.lock().unwrap();
+        while *busy {
+            debug!("Waiting for the model load in progress");
+            busy = self.finished.wait(busy).unwrap();
+        }
+        *busy = true;
+        drop(busy);
+
+        let _release = Release(self);
+        load()
+    }
+}
+
+/// Tells the idle watcher when the model has gone unused for the unload
+/// timeout. Activity and timeout changes wake the watcher so it always sleeps
+/// until the current deadline, instead of polling.
+#[derive(Default)]
+struct IdleScheduler {
+    state: Mutex<IdleState>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct IdleState {
+    /// `None` never unloads.
+    timeout: Option<Duration>,
+    /// End of the last use, or `None` once the deadline has been reported.
+    last_activity: Option<Instant>,
+    /// Transcriptions currently running; no deadline applies while non-zero.
+    busy: usize,
+    shutdown: bool,
+}
+
+impl IdleScheduler {
+    fn update(&self, change: impl FnOnce(&mut IdleState)) {
+        change(&mut self.state.lock().unwrap());
+        self.changed.notify_all();
+    }
+
+    fn touch(&self) {
+        self.update(|state| state.last_activity = Some(Instant::now()));
+    }
+
+    fn set_timeout(&self, timeout: Option<Duration>) {
+        self.update(|state| state.timeout = timeout);
+    }
+
+    fn shutdown(&self) {
+        self.update(|state| state.shutdown = true);
+    }
+
+    /// Marks the model as in use until the guard is dropped. The idle time
+    /// starts counting when the last guard is released.
+    fn busy(self: &Arc<Self>) -> BusyGuard {
+        self.update(|state| state.busy += 1);
+        BusyGuard(self.clone())
+    }
+
+    /// Blocks until the model has been idle for the timeout. Each period of
+    /// activity is reported once. Returns `false` after `shutdown`.
+    fn wait_for_idle(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if state.shutdown {
+                return false;
+            }
+
+            let deadline = match (state.timeout, state.last_activity) {
+                (Some(timeout), Some(last_activity)) if state.busy == 0 => last_activity + timeout,
+                _ => {
+                    state = self.changed.wait(state).unwrap();
+                    continue;
+                }
+            };
+
+            let now = Instant::now();
+            if now >= deadline {
+                state.last_activity = None;
+                return true;
+            }
+            state = self.changed.wait_timeout(state, deadline - now).unwrap().0;
+        }
+    }
+
+    /// Runs `unload` unless the model was used again since `wait_for_idle`
+    /// reported it idle, or is in use now. The state stays locked meanwhile,
+    /// so a transcription starting concurrently waits for the unload to
+    /// finish and then loads the model again.
+    fn run_if_idle<T>(&self, unload: impl FnOnce() -> T) -> Option<T> {
+        let state = self.state.lock().unwrap();
+        if state.busy > 0 || state.last_activity.is_some() || state.timeout.is_none() {
+            return None;
+        }
+        Some(unload())
+    }
+}
+
+struct BusyGuard(Arc<IdleScheduler>);
+
+impl Drop for BusyGuard {
+    fn drop(&mut self) {
+        self.0.update(|state| {
+            state.busy -= 1;
+            state.last_activity = Some(Instant::now());
+        });
+    }
+}
+
+/// A loaded engine shared between the manager and running transcriptions.
+type SharedEngine = Arc<Mutex<Box<dyn SpeechEngine>>>;
+
+/// Transcribes and post-processes audio with the model picked for
+/// `TranscriptionManager::with_model`.
+pub type Transcriber<'a> =
+    dyn Fn(Vec<f32>, &TranscriptionOptions, &CancellationToken) -> Result<TranscriptionResult> + 'a;
+
+/// A model that is kept loaded, as reported by `get_model_load_status`.
+#[derive(Clone, Debug, Serialize)]
+pub struct ResidentModelInfo {
+    pub model_id: String,
+    /// Approximate memory use, taken from the model's size on disk.
+    pub size_mb: u64,
+}
+
+/// Loaded models keyed by model id, ordered from least to most recently used.
+struct ResidentModels<T> {
+    entries: Vec<(ResidentModelInfo, T)>,
+}
+
+impl<T> Default for ResidentModels<T> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<T: Clone> ResidentModels<T> {
+    fn contains(&self, model_id: &str) -> bool {
+        self.entries
+            .iter()
+            .any(|(info, _)| info.model_id == model_id)
+    }
+
+    /// Returns the model without marking it as used.
+    fn get(&self, model_id: &str) -> Option<T> {
+        self.entries
+            .iter()
+            .find(|(info, _)| info.model_id == model_id)
+            .map(|(_, model)| model.clone())
+    }
+
+    /// Returns the model and marks it as most recently used.
+    fn touch(&mut self, model_id: &str) -> Option<T> {
+        let index = self
+            .entries
+            .iter()
+            .position(|(info, _)| info.model_id == model_id)?;
+        let entry = self.entries.remove(index);
+        let model = entry.1.clone();
+        self.entries.push(entry);
+        Some(model)
+    }
+
+    /// Adds a model as most recently used, replacing one with the same id.
+    fn insert(&mut self, model_id: &str, size_mb: u64, model: T) {
+        self.remove(model_id);
+        self.entries.push((
+            ResidentModelInfo {
+                model_id: model_id.to_string(),
+                size_mb,
+            },
+            model,
+        ));
+    }
+
+    fn remove(&mut self, model_id: &str) -> Option<T> {
+        let index = self
+            .entries
+            .iter()
+            .position(|(info, _)| info.model_id == model_id)?;
+        Some(self.entries.remove(index).1)
+    }
+
+    fn drain(&mut self) -> Vec<T> {
+        self.entries.drain(..).map(|(_, model)| model).collect()
+    }
+
+    /// Drops least recently used models until the rest fit in `budget_mb`.
+    /// The most recently used model is always kept, even if it alone is over
+    /// budget. Returns the ids of the evicted models.
+    fn evict_over_budget(&mut self, budget_mb: u64) -> Vec<String> {
+        let mut evicted = Vec::new();
+        while self.entries.len() > 1 && self.footprint_mb() > budget_mb {
+            evicted.push(self.entries.remove(0).0.model_id);
+        }
+        evicted
+    }
+
+    /// Drops least recently used models other than `keep` until another
+    /// `size_mb` fits in `budget_mb`. Returns the ids of the evicted models,
+    /// or `None` without evicting anything if it can't fit next to `keep`.
+    fn make_room(
+        &mut self,
+        size_mb: u64,
+        budget_mb: u64,
+        keep: Option<&str>,
+    ) -> Option<Vec<String>> {
+        let kept_mb: u64 = self
+            .entries
+            .iter()
+            .filter(|(info, _)| Some(info.model_id.as_str()) == keep)
+            .map(|(info, _)| info.size_mb)
+            .sum();
+        if kept_mb + size_mb > budget_mb {
+            return None;
+        }
+
+        let mut evicted = Vec::new();
+        while self.footprint_mb() + size_mb > budget_mb {
+            let index = self
+                .entries
+                .iter()
+                .position(|(info, _)| Some(info.model_id.as_str()) != keep)?;
+            evicted.push(self.entries.remove(index).0.model_id);
+        }
+        Some(evicted)
+    }
+
+    fn footprint_mb(&self) -> u64 {
+        self.entries.iter().map(|(info, _)| info.size_mb).sum()
+    }
+
+    fn models(&self) -> Vec<ResidentModelInfo> {
+        self.entries.iter().map(|(info, _)| info.clone()).collect()
+    }
+}
+
+/// A stopped dictation waiting for `complete_stream`.
+pub struct PendingDictation {
+    session: Option<StreamSession>,
+    cancel: CancellationToken,
+}
+
+impl PendingDictation {
+    /// Token that cancels this dictation, including a transcription of it
+    /// that is already running or still waiting.
+    pub fn cancel_token(&self) -> CancellationToken {
+        self.cancel.clone()
+    }
+
+    /// Drops a dictation that will not be transcribed, stopping any segment
+    /// transcription still running for it.
+    pub fn discard(self) {
+        self.cancel.cancel();
+        if let Some(session) = self.session {
+            session.cancel.cancel();
+        }
+    }
+}
+
+/// Progress of a streaming dictation, owned by the segment worker thread.
+#[derive(Default)]
+struct StreamProgress {
+    /// Number of leading samples of the recording already transcribed.
+    consumed_samples: usize,
+    result: TranscriptionResult,
+    failed: bool,
+}
+
+/// A dictation whose closed VAD segments are transcribed while the user is
+/// still speaking.
+struct StreamSession {
+    segment_tx: mpsc::Sender<Vec<f32>>,
+    cancel: CancellationToken,
+    worker: thread::JoinHandle<StreamProgress>,
+    /// Keeps the idle watcher from unloading the model between segments,
+    /// from the start of the recording until the session is completed or
+    /// dropped.
+    _busy: BusyGuard,
 }
 
 #[derive(Clone)]
 pub struct TranscriptionManager {
-    engine: Arc<Mutex<Option<LoadedEngine>>>,
+    /// Loaded engines, evicted least recently used first once they exceed
+    /// the memory budget.
+    resident: Arc<Mutex<ResidentModels<SharedEngine>>>,
+    engines: Arc<EngineRegistry>,
     model_manager: Arc<ModelManager>,
     app_handle: AppHandle,
     current_model_id: Arc<Mutex<Option<String>>>,
-    last_activity: Arc<AtomicU64>,
-    shutdown_signal: Arc<AtomicBool>,
+    active_stream: Arc<Mutex<Option<StreamSession>>>,
+    /// Cancels the dictation that was started last, including any
+    /// transcription of it that is still running.
+    dictation_cancel: Arc<Mutex<CancellationToken>>,
+    /// Final text of the last dictation and when it finished, used as prompt
+    /// context for the next one when carry-over is enabled.
+    previous_dictation: Arc<Mutex<Option<(String, Instant)>>>,
+    load_gate: Arc<LoadGate>,
+    idle: Arc<IdleScheduler>,
     watcher_handle: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
 }
 
@@ -47,74 +843,77 @@ impl TranscriptionManager {
     pub fn new(app: &App, model_manager: Arc<ModelManager>) -> Result<Self> {
         let app_handle = app.app_handle().clone();
 
+        let mut engines = EngineRegistry::with_builtin_engines();
+        engines.register(EngineType::Parakeet, {
+            let app_handle = app_handle.clone();
+            move || {
+                Box::new(ParakeetSpeechEngine::new(
+                    get_settings(&app_handle).parakeet_precision,
+                ))
+            }
+        });
+        engines.register(EngineType::Remote, {
+            let app_handle = app_handle.clone();
+            move || {
+                Box::new(RemoteSpeechEngine::new(
+                    get_settings(&app_handle).remote_engine,
+                ))
+            }
+        });
+
+        let settings = get_settings(&app_handle);
         let manager = Self {
-            engine: Arc::new(Mutex::new(None)),
+            resident: Arc::new(Mutex::new(ResidentModels::default())),
+            engines: Arc::new(engines),
             model_manager,
             app_handle: app_handle.clone(),
             current_model_id: Arc::new(Mutex::new(None)),
-            last_activity: Arc::new(AtomicU64::new(
-                SystemTime::now()
-                    .duration_since(SystemTime::UNIX_EPOCH)
-                    .unwrap()
-                    .as_millis() as u64,
-            )),
-            shutdown_signal: Arc::new(AtomicBool::new(false)),
+            active_stream: Arc::new(Mutex::new(None)),
+            dictation_cancel: Arc::new(Mutex::new(CancellationToken::default())),
+            previous_dictation: Arc::new(Mutex::new(None)),
+            load_gate: Arc::new(LoadGate::default()),
+            idle: Arc::new(IdleScheduler::default()),
             watcher_handle: Arc::new(Mutex::new(None)),
         };
 
         // Start the idle watcher
+        manager.set_unload_timeout(settings.model_unload_timeout);
         {
             let app_handle_cloned = app_handle.clone();
             let manager_cloned = manager.clone();
-            let shutdown_signal = manager.shutdown_signal.clone();
+            let idle = manager.idle.clone();
             let handle = thread::spawn(move || {
-                while !shutdown_signal.load(Ordering::Relaxed) {
-                    thread::sleep(Duration::from_secs(10)); // Check every 10 seconds
-
-                    // Check shutdown signal again after sleep
-                    if shutdown_signal.load(Ordering::Relaxed) {
-                        break;
+                while idle.wait_for_idle() {
+                    if !manager_cloned.is_model_loaded() {
+                        continue;
                     }
 
-                    let settings = get_settings(&app_handle_cloned);
-                    let timeout_seconds = settings.model_unload_timeout.to_seconds();
-
-                    if let Some(limit_seconds) = timeout_seconds {
-                        // Skip polling-based unloading for immediate timeout since it's handled directly in transcribe()
-                        if settings.model_unload_timeout == ModelUnloadTimeout::Immediately {
-                            continue;
-                        }
+                    let unload_start = std::time::Instant::now();
+                    debug!("Starting to unload model due to inactivity");
 
-                        let last = manager_cloned.last_activity.load(Ordering::Relaxed);
-                        let now_ms = SystemTime::now()
-                            .duration_since(SystemTime::UNIX_EPOCH)
-                            .unwrap()
-                            .as_millis() as u64;
-
-                        if now_ms.saturating_sub(last) > limit_seconds * 1000 {
-                            // idle -> unload
-                            if manager_cloned.is_model_loaded() {
-                                let unload_start = std::time::Instant::now();
-                                debug!("Starting to unload model due to inactivity");
-
-                                if let Ok(()) = manager_cloned.unload_model() {
-                                    let _ = app_handle_cloned.emit(
-                                        "model-state-changed",
-                                        ModelStateEvent {
-                                            event_type: "unloaded_due_to_idle".to_string(),
-                                            model_id: None,
-                                            model_name: None,
-                                            error: None,
-                                        },
-                                    );
-                                    let unload_duration = unload_start.elapsed();
-                                    debug!(
-                                        "Model unloaded due to inactivity (took {}ms)",
-                                        unload_duration.as_millis()
-                                    );
-                                }
-                            }
-                        }
+                    // Behind the load gate, and only if nothing used the model
+                    // since the deadline passed
+                    let unloaded = manager_cloned
+                        .load_gate
+                        .run(|| idle.run_if_idle(|| manager_cloned.unload_model_now()));
+                    if unloaded.is_none() {
+                        debug!("Model was used again, keeping it loaded");
+                    }
+                    if let Some(Ok(())) = unloaded {
+                        let _ = app_handle_cloned.emit(
+                            "model-state-changed",
+                            ModelStateEvent {
+                                event_type: "unloaded_due_to_idle".to_string(),
+                                model_id: None,
+                                model_name: None,
+                                error: None,
+                            },
+                        );
+                        let unload_duration = unload_start.elapsed();
+                        debug!(
+                            "Model unloaded due to inactivity (took {}ms)",
+                            unload_duration.as_millis()
+                        );
                     }
                 }
                 debug!("Idle watcher thread shutting down gracefully");
@@ -123,30 +922,38 @@ impl TranscriptionManager {
         }
 
         // Try to load the default model from settings, but don't fail if no models are available
-        let settings = get_settings(&app_handle);
         let _ = manager.load_model(&settings.selected_model);
 
         Ok(manager)
     }
 
     pub fn is_model_loaded(&self) -> bool {
-        let engine = self.engine.lock().unwrap();
-        engine.is_some()
+        self.get_current_model()
+            .is_some_and(|model_id| self.resident.lock().unwrap().contains(&model_id))
+    }
+
+    /// Models currently kept loaded, least recently used first.
+    pub fn resident_models(&self) -> Vec<ResidentModelInfo> {
+        self.resident.lock().unwrap().models()
+    }
+
+    /// Engine of the active model, marked as most recently used.
+    fn active_engine(&self) -> Option<SharedEngine> {
+        let model_id = self.get_current_model()?;
+        self.resident.lock().unwrap().touch(&model_id)
     }
 
     pub fn unload_model(&self) -> Result<()> {
+        self.load_gate.run(|| self.unload_model_now())
+    }
+
+    fn unload_model_now(&self) -> Result<()> {
         let unload_start = std::time::Instant::now();
         debug!("Starting to unload model");
 
-        {
-            let mut engine = self.engine.lock().unwrap();
-            if let Some(ref mut loaded_engine) = *engine {
-                match loaded_engine {
-                    LoadedEngine::Whisper(ref mut whisper) => whisper.unload_model(),
-                    LoadedEngine::Parakeet(ref mut parakeet) => parakeet.unload_model(),
-                }
-            }
-            *engine = None; // Drop the engine to free memory
+        let engines = self.resident.lock().unwrap().drain();
+        for engine in engines {
+            engine.lock().unwrap().unload();
         }
         {
             let mut current_model = self.current_model_id.lock().unwrap();
@@ -173,6 +980,10 @@ impl TranscriptionManager {
     }
 
     pub fn load_model(&self, model_id: &str) -> Result<()> {
+        self.load_gate.run(|| self.load_model_now(model_id))
+    }
+
+    fn load_model_now(&self, model_id: &str) -> Result<()> {
         let load_start = std::time::Instant::now();
         debug!("Starting to load model: {}", model_id);
 
@@ -206,58 +1017,68 @@ impl TranscriptionManager {
             return Err(anyhow::anyhow!(error_msg));
         }
 
+        // Switching back to a model that is still resident needs no load
+        if self.resident.lock().unwrap().touch(model_id).is_some() {
+            *self.current_model_id.lock().unwrap() = Some(model_id.to_string());
+            let _ = self.app_handle.emit(
+                "model-state-changed",
+                ModelStateEvent {
+                    event_type: "loading_completed".to_string(),
+                    model_id: Some(model_id.to_string()),
+                    model_name: Some(model_info.name.clone()),
+                    error: None,
+                },
+            );
+            debug!("Switched to resident model: {}", model_id);
+            return Ok(());
+        }
+
         let model_path = self.model_manager.get_model_path(model_id)?;
 
-        // Create appropriate engine based on model type
-        let loaded_engine = match model_info.engine_type {
-            EngineType::Whisper => {
-                let mut engine = WhisperEngine::new();
-                engine.load_model(&model_path).map_err(|e| {
-                    let error_msg = format!("Failed to load whisper model {}: {}", model_id, e);
-                    let _ = self.app_handle.emit(
-                        "model-state-changed",
-                        ModelStateEvent {
-                            event_type: "loading_failed".to_string(),
-                            model_id: Some(model_id.to_string()),
-                            model_name: Some(model_info.name.clone()),
-                            error: Some(error_msg.clone()),
-                        },
-                    );
-                    anyhow::anyhow!(error_msg)
+        // Create the engine registered for this model type
+        let load_result = self
+            .engines
+            .create(model_info.engine_type)
+            .and_then(|mut engine| {
+                engine.load(&model_path).map_err(|e| {
+                    anyhow::anyhow!(
+                        "Failed to load {} model {}: {}",
+                        engine.name().to_lowercase(),
+                        model_id,
+                        e
+                    )
                 })?;
-                LoadedEngine::Whisper(engine)
-            }
-            EngineType::Parakeet => {
-                let mut engine = ParakeetEngine::new();
-                engine
-                    .load_model_with_params(&model_path, ParakeetModelParams::int8())
-                    .map_err(|e| {
-                        let error_msg =
-                            format!("Failed to load parakeet model {}: {}", model_id, e);
-                        let _ = self.app_handle.emit(
-                            "model-state-changed",
-                            ModelStateEvent {
-                                event_type: "loading_failed".to_string(),
-                                model_id: Some(model_id.to_string()),
-                                model_name: Some(model_info.name.clone()),
-                                error: Some(error_msg.clone()),
-                            },
-                        );
-                        anyhow::anyhow!(error_msg)
-                    })?;
-                LoadedEngine::Parakeet(engine)
+                Ok(engine)
+            });
+        let loaded_engine = match load_result {
+            Ok(engine) => engine,
+            Err(e) => {
+                let _ = self.app_handle.emit(
+                    "model-state-changed",
+                    ModelStateEvent {
+                        event_type: "loading_failed".to_string(),
+                        model_id: Some(model_id.to_string()),
+                        model_name: Some(model_info.name.clone()),
+                        error: Some(e.to_string()),
+                    },
+                );
+                return Err(e);
             }
         };
 
-        // Update the current engine and model ID
-        {
-            let mut engine = self.engine.lock().unwrap();
-            *engine = Some(loaded_engine);
-        }
+        // Keep the engine resident and make it the active model
+        self.resident.lock().unwrap().insert(
+            model_id,
+            model_info.size_mb,
+            Arc::new(Mutex::new(loaded_engine)),
+        );
         {
             let mut current_model = self.current_model_id.lock().unwrap();
             *current_model = Some(model_id.to_string());
         }
+        self.enforce_memory_budget();
+        // A fresh model counts as used, so the idle timeout starts now
+        self.touch_activity();
 
         // Emit loading completed event
         let _ = self.app_handle.emit(
@@ -284,116 +1105,478 @@ impl TranscriptionManager {
         current_model.clone()
     }
 
-    pub fn transcribe(&self, audio: Vec<f32>) -> Result<String> {
-        // Update last activity timestamp
-        self.last_activity.store(
-            SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_millis() as u64,
-            Ordering::Relaxed,
-        );
+    /// Evicts least recently used models until the resident set fits in
+    /// `model_memory_budget_mb`. The active model is always the most recently
+    /// used one, so it is never evicted.
+    pub fn enforce_memory_budget(&self) {
+        let budget_mb = get_settings(&self.app_handle).model_memory_budget_mb;
+        let mut resident = self.resident.lock().unwrap();
+        // Evicted engines still in use by a transcription are freed once it
+        // finishes and drops its handle
+        for model_id in resident.evict_over_budget(budget_mb) {
+            debug!(
+                "Evicted model {} to stay within {} MB (resident: {} MB)",
+                model_id,
+                budget_mb,
+                resident.footprint_mb()
+            );
+        }
+    }
 
-        let st = std::time::Instant::now();
+    /// Evicts resident models other than the active one, least recently used
+    /// first, until a model of `size_mb` fits in `model_memory_budget_mb`.
+    /// Fails without evicting anything if it can't fit next to the active
+    /// model.
+    fn make_room_for(&self, size_mb: u64) -> Result<()> {
+        let budget_mb = get_settings(&self.app_handle).model_memory_budget_mb;
+        let current_model = self.current_model_id.lock().unwrap().clone();
+        let evicted = self
+            .resident
+            .lock()
+            .unwrap()
+            .make_room(size_mb, budget_mb, current_model.as_deref())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "A {} MB model does not fit next to the active model within the {} MB memory budget",
+                    size_mb,
+                    budget_mb
+                )
+            })?;
+        for model_id in evicted {
+            debug!(
+                "Evicted model {} to fit {} MB within {} MB",
+                model_id, size_mb, budget_mb
+            );
+        }
+        Ok(())
+    }
 
-        println!("Audio vector length: {}", audio.len());
+    /// Drops a single resident model, e.g. after its configuration changed.
+    pub fn evict_model(&self, model_id: &str) {
+        if self.resident.lock().unwrap().remove(model_id).is_none() {
+            return;
+        }
+        let mut current_model = self.current_model_id.lock().unwrap();
+        if current_model.as_deref() == Some(model_id) {
+            *current_model = None;
+        }
+        debug!("Evicted model {}", model_id);
+    }
 
-        if audio.is_empty() {
-            println!("Empty audio vector");
-            return Ok(String::new());
+    /// Evicts every resident model that runs on `engine_type`, so the next
+    /// load picks up changed engine settings.
+    pub fn evict_engine_models(&self, engine_type: EngineType) {
+        for model in self.resident_models() {
+            let runs_on_engine = self
+                .model_manager
+                .get_model_info(&model.model_id)
+                .is_some_and(|info| info.engine_type == engine_type);
+            if runs_on_engine {
+                self.evict_model(&model.model_id);
+            }
         }
+    }
 
-        // Check if model is loaded, if not try to load it
-        {
-            let engine_guard = self.engine.lock().unwrap();
-            if engine_guard.is_none() {
-                // Model not loaded, try to load the selected model from settings
-                let settings = get_settings(&self.app_handle);
-                println!(
-                    "Model not loaded, attempting to load: {}",
-                    settings.selected_model
-                );
+    fn touch_activity(&self) {
+        self.idle.touch();
+    }
 
-                // Drop the guard before calling load_model to avoid deadlock
-                drop(engine_guard);
+    /// Applies a new unload timeout; the idle watcher picks it up right away.
+    pub fn set_unload_timeout(&self, timeout: ModelUnloadTimeout) {
+        self.idle.set_timeout(timeout.to_duration());
+    }
 
-                // Try to load the model
-                if let Err(e) = self.load_model(&settings.selected_model) {
-                    return Err(anyhow::anyhow!(
-                        "Failed to auto-load model '{}': {}. Please check that the model is downloaded and try again.",
-                        settings.selected_model, e
-                    ));
-                }
+    /// Loads the selected model unless it is loaded already. If a load is
+    /// running, e.g. a preload started with the recording, this waits for it
+    /// instead of starting another one.
+    fn ensure_model_loaded(&self) -> Result<()> {
+        self.load_gate.run(|| {
+            if self.is_model_loaded() {
+                return Ok(());
             }
+
+            // Model not loaded, try to load the selected model from settings
+            let settings = get_settings(&self.app_handle);
+            println!(
+                "Model not loaded, attempting to load: {}",
+                settings.selected_model
+            );
+
+            self.load_model_now(&settings.selected_model).map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to auto-load model '{}': {}. Please check that the model is downloaded and try again.",
+                    settings.selected_model, e
+                )
+            })
+        })
+    }
+
+    /// Starts loading the selected model in the background when it is not
+    /// loaded, so the load overlaps with the user speaking. The streaming
+    /// session from `begin_stream` keeps the model loaded until the
+    /// recording is transcribed.
+    pub fn preload_model(&self) {
+        if self.is_model_loaded() {
+            return;
         }
 
+        let app_handle = self.app_handle.clone();
+        thread::spawn(move || {
+            let tm = app_handle.state::<Arc<TranscriptionManager>>();
+            match tm.ensure_model_loaded() {
+                Ok(()) => debug!("Model preloaded while recording"),
+                Err(e) => debug!("Speculative model load failed: {}", e),
+            }
+        });
+    }
+
+    /// Runs the loaded engine on `audio` and returns its raw output, without
+    /// any post-processing.
+    fn transcribe_raw(
+        &self,
+        audio: &[f32],
+        options: &TranscriptionOptions,
+        cancel: &CancellationToken,
+    ) -> Result<TranscriptionResult> {
+        // Keeps the idle watcher from unloading the engine mid-transcription
+        let _busy = self.idle.busy();
+        self.ensure_model_loaded()?;
+
+        let engine = self.active_engine().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Model failed to load after auto-load attempt. Please check your model settings."
+            )
+        })?;
+        let model_id = self.get_current_model().unwrap_or_default();
+
         // Get current settings for configuration
         let settings = get_settings(&self.app_handle);
+        let carried_context = self.carried_context(&settings, options);
+        transcribe_chunks(
+            &engine,
+            audio,
+            &model_id,
+            &settings,
+            options,
+            &carried_context,
+            cancel,
+        )
+    }
 
-        // Perform transcription with the appropriate engine
-        let result = {
-            let mut engine_guard = self.engine.lock().unwrap();
-            let engine = engine_guard.as_mut().ok_or_else(|| {
-                anyhow::anyhow!(
-                    "Model failed to load after auto-load attempt. Please check your model settings."
-                )
-            })?;
+    /// Capabilities of the engine that runs models of `engine_type`.
+    pub fn engine_capabilities(&self, engine_type: EngineType) -> Result<EngineCapabilities> {
+        Ok(self.engines.create(engine_type)?.capabilities())
+    }
 
-            match engine {
-                LoadedEngine::Whisper(whisper_engine) => {
-                    let params = WhisperInferenceParams {
-                        language: if settings.selected_language == "auto" {
-                            None
-                        } else {
-                            Some(settings.selected_language.clone())
-                        },
-                        ..Default::default()
-                    };
-
-                    whisper_engine
-                        .transcribe_samples(audio, Some(params))
-                        .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))?
-                }
-                LoadedEngine::Parakeet(parakeet_engine) => {
-                    let params = ParakeetInferenceParams {
-                        timestamp_granularity: TimestampGranularity::Segment,
-                    };
-
-                    parakeet_engine
-                        .transcribe_samples(audio, Some(params))
-                        .map_err(|e| anyhow::anyhow!("Parakeet transcription failed: {}", e))?
-                }
+    /// Loads `model_id` into a fresh engine, apart from the resident models
+    /// and without switching the active one. Runs behind the load gate like
+    /// any other load, and first evicts inactive resident models as needed to
+    /// keep the extra copy within the memory budget. The active model is
+    /// never evicted for it; the load fails instead.
+    fn load_private_engine(&self, model_id: &str) -> Result<Box<dyn SpeechEngine>> {
+        let model_info = self
+            .model_manager
+            .get_model_info(model_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+        let model_path = self.model_manager.get_model_path(model_id)?;
+
+        self.load_gate.run(|| {
+            self.make_room_for(model_info.size_mb)?;
+            let mut engine = self.engines.create(model_info.engine_type)?;
+            engine.load(&model_path)?;
+            Ok(engine)
+        })
+    }
+
+    /// Transcribes `audio` once with `model_id` and returns how long the
+    /// load and the transcription took. A resident model is measured as is,
+    /// so there is no load time; any other one is loaded into a private
+    /// engine that is unloaded again afterwards.
+    pub fn measure_model(
+        &self,
+        model_id: &str,
+        audio: &[f32],
+    ) -> Result<(Option<Duration>, Duration)> {
+        // Keeps the idle watcher from unloading a resident engine meanwhile
+        let _busy = self.idle.busy();
+        let resident = self.resident.lock().unwrap().get(model_id);
+        let (engine, load_time) = match resident {
+            Some(engine) => (engine, None),
+            None => {
+                let load_start = Instant::now();
+                let engine = self.load_private_engine(model_id)?;
+                (Arc::new(Mutex::new(engine)), Some(load_start.elapsed()))
             }
         };
 
-        // Apply word correction if custom words are configured
-        let corrected_result = if !settings.custom_words.is_empty() {
-            apply_custom_words(
-                &result.text,
-                &settings.custom_words,
-                settings.word_correction_threshold,
-            )
-        } else {
-            result.text
+        let settings = get_settings(&self.app_handle);
+        let mut engine = engine.lock().unwrap();
+        let request = InferenceRequest {
+            params: inference_params(model_id, &settings, &engine.capabilities()),
+            ..Default::default()
         };
+        let transcribe_start = Instant::now();
+        let result = engine.transcribe(audio.to_vec(), &request, &CancellationToken::default());
+        let transcribe_time = transcribe_start.elapsed();
+        if load_time.is_some() {
+            engine.unload();
+        }
+
+        result?;
+        Ok((load_time, transcribe_time))
+    }
+
+    /// Runs `run` with a transcriber for `model_id` that leaves the active
+    /// model selected and loaded: a resident model is shared, any other one
+    /// is loaded into a private engine that is dropped once `run` returns.
+    /// Fails if that engine doesn't fit next to the active model. No context
+    /// is carried over to or from these transcriptions.
+    pub fn with_model<T>(&self, model_id: &str, run: impl FnOnce(&Transcriber) -> T) -> Result<T> {
+        // Keeps the idle watcher from unloading a shared engine meanwhile
+        let _busy = self.idle.busy();
+        let resident = self.resident.lock().unwrap().get(model_id);
+        let engine = match resident {
+            Some(engine) => engine,
+            None => Arc::new(Mutex::new(self.load_private_engine(model_id)?)),
+        };
+
+        let settings = get_settings(&self.app_handle);
+        let transcribe = |audio: Vec<f32>,
+                          options: &TranscriptionOptions,
+                          cancel: &CancellationToken|
+         -> Result<TranscriptionResult> {
+            let result =
+                transcribe_chunks(&engine, &audio, model_id, &settings, options, "", cancel)?;
+            Ok(post_process(result, audio.len(), &settings, options))
+        };
+        Ok(run(&transcribe))
+    }
+
+    /// Returns the previous dictation if carry-over is enabled and it finished
+    /// within the configured idle gap. Expired context is dropped.
+    fn carried_context(&self, settings: &AppSettings, options: &TranscriptionOptions) -> String {
+        if !settings.context_carry_over || options.standalone {
+            return String::new();
+        }
+
+        let mut previous = self.previous_dictation.lock().unwrap();
+        let max_idle = Duration::from_secs(settings.context_carry_over_idle_seconds);
+        match previous.as_ref() {
+            Some((text, finished_at)) if finished_at.elapsed() <= max_idle => text.clone(),
+            Some(_) => {
+                debug!("Previous dictation is older than the idle gap, not carrying it over");
+                *previous = None;
+                String::new()
+            }
+            None => String::new(),
+        }
+    }
+
+    /// Post-processes raw engine output once a dictation of `speech_samples`
+    /// samples is complete, and keeps it as context for the next dictation.
+    fn finish_transcription(
+        &self,
+        result: TranscriptionResult,
+        speech_samples: usize,
+        options: &TranscriptionOptions,
+        st: std::time::Instant,
+    ) -> TranscriptionResult {
+        let settings = get_settings(&self.app_handle);
+        let result = post_process(result, speech_samples, &settings, options);
 
         let et = std::time::Instant::now();
-        let translation_note = if settings.translate_to_english {
+        let translation_note = if options.translate(&settings) {
             " (translated)"
         } else {
             ""
         };
         println!("\ntook {}ms{}", (et - st).as_millis(), translation_note);
 
-        // Check if we should immediately unload the model after transcription
-        if settings.model_unload_timeout == ModelUnloadTimeout::Immediately {
-            println!("⚡ Immediately unloading model after transcription");
-            if let Err(e) = self.unload_model() {
-                eprintln!("Failed to immediately unload model: {}", e);
+        if settings.context_carry_over && !options.standalone && !result.text.is_empty() {
+            *self.previous_dictation.lock().unwrap() = Some((result.text.clone(), Instant::now()));
+        }
+
+        result
+    }
+
+    pub fn transcribe(&self, audio: Vec<f32>) -> Result<TranscriptionResult> {
+        self.transcribe_with_options(
+            audio,
+            &TranscriptionOptions::default(),
+            &CancellationToken::default(),
+        )
+    }
+
+    /// Transcribes audio that was not dictated, such as a file, keeping it out
+    /// of the context carried over between dictations.
+    pub fn transcribe_standalone(&self, audio: Vec<f32>) -> Result<TranscriptionResult> {
+        let options = TranscriptionOptions {
+            standalone: true,
+            ..Default::default()
+        };
+        self.transcribe_with_options(audio, &options, &CancellationToken::default())
+    }
+
+    /// Same as `transcribe`, with per-request overrides of the settings. Fails
+    /// with `TranscriptionCancelled` once `cancel` is triggered.
+    pub fn transcribe_with_options(
+        &self,
+        audio: Vec<f32>,
+        options: &TranscriptionOptions,
+        cancel: &CancellationToken,
+    ) -> Result<TranscriptionResult> {
+        // Held before anything else, so the idle deadline can't pass between
+        // here and the engine picking up the audio
+        let _busy = self.idle.busy();
+
+        let st = std::time::Instant::now();
+
+        println!("Audio vector length: {}", audio.len());
+
+        if audio.is_empty() {
+            println!("Empty audio vector");
+            return Ok(TranscriptionResult::default());
+        }
+
+        let raw_result = self.transcribe_raw(&audio, options, cancel)?;
+        Ok(self.finish_transcription(raw_result, audio.len(), options, st))
+    }
+
+    /* ---------- streaming -------------------------------------------------- */
+
+    /// Starts a streaming session for a new recording, replacing any previous
+    /// one. Segments pushed with `push_stream_segment` are transcribed in the
+    /// background and reported through `transcription-partial` events.
+    pub fn begin_stream(&self) {
+        let busy = self.idle.busy();
+        let (segment_tx, segment_rx) = mpsc::channel::<Vec<f32>>();
+        let app_handle = self.app_handle.clone();
+
+        let cancel = CancellationToken::default();
+        *self.dictation_cancel.lock().unwrap() = cancel.clone();
+        let worker_cancel = cancel.clone();
+
+        let worker = thread::spawn(move || {
+            let mut progress = StreamProgress::default();
+            while let Ok(segment) = segment_rx.recv() {
+                if progress.failed || worker_cancel.is_cancelled() {
+                    // Everything after a failed segment is left for the tail
+                    continue;
+                }
+
+                let tm = app_handle.state::<Arc<TranscriptionManager>>();
+                let segment_len = segment.len();
+                match tm.transcribe_raw(&segment, &TranscriptionOptions::default(), &worker_cancel)
+                {
+                    Ok(part) => {
+                        let offset = progress.consumed_samples as f32
+                            / constants::WHISPER_SAMPLE_RATE as f32;
+                        progress.result.append(part, offset, 0);
+                        progress.consumed_samples += segment_len;
+                        let _ = app_handle.emit(
+                            "transcription-partial",
+                            TranscriptionPartialEvent {
+                                text: progress.result.text.clone(),
+                            },
+                        );
+                    }
+                    Err(e) if e.is::<TranscriptionCancelled>() => {}
+                    Err(e) => {
+                        eprintln!("Streaming segment transcription failed: {}", e);
+                        progress.failed = true;
+                    }
+                }
+            }
+            progress
+        });
+
+        let previous = self.active_stream.lock().unwrap().replace(StreamSession {
+            segment_tx,
+            cancel,
+            worker,
+            _busy: busy,
+        });
+        if previous.is_some() {
+            debug!("Replaced an unfinished streaming session");
+        }
+    }
+
+    /// Queues a closed speech segment of the current recording for
+    /// transcription. Ignored when no streaming session is active.
+    pub fn push_stream_segment(&self, segment: Vec<f32>) {
+        if let Some(session) = self.active_stream.lock().unwrap().as_ref() {
+            debug!("Queueing streaming segment of {} samples", segment.len());
+            let _ = session.segment_tx.send(segment);
+        }
+    }
+
+    /// Cancels the current dictation. Its streaming session is dropped without
+    /// waiting for results, and a final transcription that is already running
+    /// fails with `TranscriptionCancelled` instead of returning text.
+    pub fn cancel_dictation(&self) {
+        self.dictation_cancel.lock().unwrap().cancel();
+        if let Some(session) = self.active_stream.lock().unwrap().take() {
+            session.cancel.cancel();
+            debug!("Discarded streaming session");
+        }
+    }
+
+    /// Detaches the current dictation when its recording stops, so the next
+    /// recording can begin its own stream while this one waits to be completed.
+    pub fn take_stream(&self) -> PendingDictation {
+        PendingDictation {
+            session: self.active_stream.lock().unwrap().take(),
+            cancel: self.dictation_cancel.lock().unwrap().clone(),
+        }
+    }
+
+    /// Completes a dictation taken with `take_stream`. Segments already
+    /// transcribed while the user was speaking are reused, so only the
+    /// remaining tail of `audio` still needs to go through the engine.
+    pub fn complete_stream(
+        &self,
+        pending: PendingDictation,
+        audio: Vec<f32>,
+    ) -> Result<TranscriptionResult> {
+        let PendingDictation { session, cancel } = pending;
+        let options = TranscriptionOptions::default();
+        let session = match session {
+            Some(session) => session,
+            None => return self.transcribe_with_options(audio, &options, &cancel),
+        };
+
+        // The session's busy guard keeps the model loaded until this returns
+        let st = std::time::Instant::now();
+
+        // Closing the channel lets the worker drain pending segments and exit
+        drop(session.segment_tx);
+        let progress = session
+            .worker
+            .join()
+            .map_err(|_| anyhow::anyhow!("Streaming transcription worker panicked"))?;
+
+        let mut result = progress.result;
+        let consumed = progress.consumed_samples.min(audio.len());
+        let mut tail = audio[consumed..].to_vec();
+        debug!(
+            "Finishing stream: {} samples already transcribed, {} in tail",
+            consumed,
+            tail.len()
+        );
+
+        if !tail.is_empty() {
+            // Pad short tails the same way short recordings are padded
+            let min_samples = constants::WHISPER_SAMPLE_RATE as usize;
+            if tail.len() < min_samples {
+                tail.resize(min_samples * 5 / 4, 0.0);
             }
+            let tail_result = self.transcribe_raw(&tail, &options, &cancel)?;
+            let offset = consumed as f32 / constants::WHISPER_SAMPLE_RATE as f32;
+            result.append(tail_result, offset, 0);
         }
 
-        Ok(corrected_result.trim().to_string())
+        cancel.check()?;
+        Ok(self.finish_transcription(result, audio.len(), &options, st))
     }
 }
 
@@ -402,7 +1585,7 @@ impl Drop for TranscriptionManager {
         debug!("Shutting down TranscriptionManager");
 
         // Signal the watcher thread to shutdown
-        self.shutdown_signal.store(true, Ordering::Relaxed);
+        self.idle.shutdown();
 
         // Wait for the thread to finish gracefully
         if let Some(handle) = self.watcher_handle.lock().unwrap().take() {
@@ -414,3 +1597,326 @@ impl Drop for TranscriptionManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio_toolkit::ReplacementRule;
+    use crate::engines::testing::FakeEngine;
+    use crate::settings::get_default_settings;
+    use std::path::Path;
+    use std::sync::atomic::AtomicU64;
+
+    fn word(start: f32, end: f32, text: &str) -> TranscriptWord {
+        TranscriptWord {
+            start,
+            end,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn group_words_into_segments_splits_on_sentence_end() {
+        let segments = group_words_into_segments(vec![
+            word(0.0, 0.4, "Hello"),
+            word(0.5, 0.9, "there."),
+            word(1.2, 1.6, "How"),
+            word(1.7, 2.0, "are"),
+            word(2.1, 2.4, "you"),
+        ]);
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].text, "Hello there.");
+        assert_eq!((segments[0].start, segments[0].end), (0.0, 0.9));
+        assert_eq!(segments[1].text, "How are you");
+        assert_eq!(segments[1].words.len(), 3);
+    }
+
+    #[test]
+    fn append_shifts_segments_by_offset() {
+        let mut result = TranscriptionResult {
+            text: "first".to_string(),
+            segments: vec![TranscriptSegment {
+                start: 0.0,
+                end: 1.0,
+                text: "first".to_string(),
+                words: vec![word(0.0, 1.0, "first")],
+            }],
+            ..Default::default()
+        };
+        let next = TranscriptionResult {
+            text: "second".to_string(),
+            segments: vec![TranscriptSegment {
+                start: 0.5,
+                end: 1.5,
+                text: "second".to_string(),
+                words: vec![word(0.5, 1.5, "second")],
+            }],
+            ..Default::default()
+        };
+
+        result.append(next, 10.0, 0);
+
+        assert_eq!(result.text, "first second");
+        assert_eq!(result.segments[1].start, 10.5);
+        assert_eq!(result.segments[1].words[0].end, 11.5);
+    }
+
+    #[test]
+    fn append_drops_segments_inside_overlap() {
+        let mut result = TranscriptionResult {
+            text: "one two".to_string(),
+            segments: vec![TranscriptSegment {
+                start: 0.0,
+                end: 28.0,
+                text: "one two".to_string(),
+                words: Vec::new(),
+            }],
+            ..Default::default()
+        };
+        let next = TranscriptionResult {
+            text: "two three".to_string(),
+            segments: vec![
+                TranscriptSegment {
+                    start: 0.0,
+                    end: 1.0,
+                    text: "two".to_string(),
+                    words: Vec::new(),
+                },
+                TranscriptSegment {
+                    start: 1.0,
+                    end: 3.0,
+                    text: "three".to_string(),
+                    words: Vec::new(),
+                },
+            ],
+            ..Default::default()
+        };
+
+        result.append(next, 27.0, 16);
+
+        assert_eq!(result.text, "one two three");
+        assert_eq!(result.segments.len(), 2);
+        assert_eq!(result.segments[1].text, "three");
+    }
+
+    #[test]
+    fn options_language_falls_back_to_settings() {
+        let mut settings = get_default_settings();
+        let options = TranscriptionOptions::default();
+        assert_eq!(options.language(&settings), None);
+
+        settings.language_candidates = vec!["de".to_string()];
+        assert_eq!(options.language(&settings), Some("de".to_string()));
+
+        settings.language_candidates.push("fr".to_string());
+        assert_eq!(options.language(&settings), None);
+
+        let forced = TranscriptionOptions {
+            language: Some("en".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(forced.language(&settings), Some("en".to_string()));
+    }
+
+    #[test]
+    fn registered_engine_runs_through_the_pipeline() -> Result<()> {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = EngineRegistry::empty();
+        registry.register(EngineType::Whisper, {
+            let requests = requests.clone();
+            move || {
+                Box::new(FakeEngine::new(
+                    "parse the jason",
+                    Some("de"),
+                    requests.clone(),
+                ))
+            }
+        });
+        let mut engine = registry.create(EngineType::Whisper)?;
+        engine.load(Path::new("unused"))?;
+        let engine = Mutex::new(engine);
+
+        let settings = AppSettings {
+            max_chunk_seconds: 2,
+            language_candidates: vec!["en".to_string(), "de".to_string()],
+            prompt_context: "Handy.".to_string(),
+            replacement_rules: vec![ReplacementRule {
+                pattern: "jason".to_string(),
+                replacement: "JSON".to_string(),
+                kind: Default::default(),
+                whole_word: true,
+                case_sensitive: false,
+                enabled: true,
+            }],
+            ..get_default_settings()
+        };
+        let options = TranscriptionOptions::default();
+        let cancel = CancellationToken::default();
+
+        // Three seconds of silence are split into two chunks
+        let audio = vec![0.0; 48000];
+        let raw = transcribe_chunks(&engine, &audio, "fake", &settings, &options, "", &cancel)?;
+        let result = post_process(raw, audio.len(), &settings, &options);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        for request in requests.iter() {
+            assert_eq!(request.language.as_deref(), Some("de"));
+            assert!(request
+                .initial_prompt
+                .as_deref()
+                .unwrap()
+                .contains("Handy."));
+        }
+        assert_eq!(result.text, "parse the JSON parse the JSON");
+        assert_eq!(result.language.as_deref(), Some("de"));
+        assert_eq!(result.segments.len(), 2);
+        assert_eq!(result.segments[1].text, "parse the JSON");
+        assert!((result.segments[1].end - 3.0).abs() < 1e-3);
+
+        cancel.cancel();
+        let err = transcribe_chunks(&engine, &audio, "fake", &settings, &options, "", &cancel)
+            .unwrap_err();
+        assert!(err.is::<TranscriptionCancelled>());
+        Ok(())
+    }
+
+    #[test]
+    fn cancelled_token_fails_with_typed_error() {
+        let token = CancellationToken::default();
+        assert!(token.check().is_ok());
+
+        token.clone().cancel();
+        let err = token.check().unwrap_err();
+        assert!(err.is::<TranscriptionCancelled>());
+    }
+
+    #[test]
+    fn load_gate_runs_one_load_at_a_time() {
+        let gate = Arc::new(LoadGate::default());
+        let running = Arc::new(AtomicU64::new(0));
+        let overlapped = Arc::new(AtomicBool::new(false));
+
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let (gate, running, overlapped) =
+                    (gate.clone(), running.clone(), overlapped.clone());
+                thread::spawn(move || {
+                    gate.run(|| {
+                        if running.fetch_add(1, Ordering::SeqCst) > 0 {
+                            overlapped.store(true, Ordering::SeqCst);
+                        }
+                        thread::sleep(Duration::from_millis(20));
+                        running.fetch_sub(1, Ordering::SeqCst);
+                    })
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        assert!(!overlapped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn idle_scheduler_waits_for_timeout_after_last_use() {
+        let idle = Arc::new(IdleScheduler::default());
+        idle.set_timeout(Some(Duration::from_millis(50)));
+
+        let busy = idle.busy();
+        let waiter = {
+            let idle = idle.clone();
+            thread::spawn(move || {
+                let start = Instant::now();
+                assert!(idle.wait_for_idle());
+                start.elapsed()
+            })
+        };
+        // No deadline runs while busy, so the wait covers this sleep too
+        thread::sleep(Duration::from_millis(100));
+        drop(busy);
+
+        assert!(waiter.join().unwrap() >= Duration::from_millis(150));
+    }
+
+    #[test]
+    fn idle_scheduler_wakes_on_timeout_change_and_shutdown() {
+        let idle = Arc::new(IdleScheduler::default());
+        idle.touch();
+
+        let waiter = {
+            let idle = idle.clone();
+            thread::spawn(move || (idle.wait_for_idle(), idle.wait_for_idle()))
+        };
+        // Without a timeout the waiter sleeps until one is set
+        thread::sleep(Duration::from_millis(20));
+        idle.set_timeout(Some(Duration::ZERO));
+        thread::sleep(Duration::from_millis(20));
+        idle.shutdown();
+
+        assert_eq!(waiter.join().unwrap(), (true, false));
+    }
+
+    #[test]
+    fn idle_scheduler_skips_unload_after_renewed_use() {
+        let idle = Arc::new(IdleScheduler::default());
+        idle.set_timeout(Some(Duration::ZERO));
+
+        idle.touch();
+        assert!(idle.wait_for_idle());
+        // Used again between the deadline and the unload
+        idle.touch();
+        assert_eq!(idle.run_if_idle(|| "unloaded"), None);
+
+        assert!(idle.wait_for_idle());
+        let busy = idle.busy();
+        assert_eq!(idle.run_if_idle(|| "unloaded"), None);
+        drop(busy);
+
+        assert!(idle.wait_for_idle());
+        assert_eq!(idle.run_if_idle(|| "unloaded"), Some("unloaded"));
+    }
+
+    #[test]
+    fn resident_models_evict_least_recently_used() {
+        let mut resident = ResidentModels::default();
+        resident.insert("small", 200, 1);
+        resident.insert("medium", 500, 2);
+        resident.insert("large", 1000, 3);
+        assert_eq!(resident.footprint_mb(), 1700);
+
+        // Using "small" makes "medium" the least recently used
+        assert_eq!(resident.touch("small"), Some(1));
+        assert_eq!(resident.get("medium"), Some(2));
+        assert_eq!(resident.evict_over_budget(1200), vec!["medium".to_string()]);
+        assert!(resident.contains("small") && resident.contains("large"));
+
+        // The most recently used model stays even when it alone is too big
+        assert_eq!(resident.evict_over_budget(0), vec!["large".to_string()]);
+        assert_eq!(resident.models()[0].model_id, "small");
+        assert_eq!(resident.footprint_mb(), 200);
+
+        // Room for a model outside the set never costs the kept model
+        resident.insert("medium", 500, 2);
+        resident.insert("large", 1000, 3);
+        assert_eq!(resident.make_room(100, 1800, Some("small")), Some(vec![]));
+        assert_eq!(
+            resident.make_room(300, 1800, Some("small")),
+            Some(vec!["medium".to_string()])
+        );
+        assert_eq!(resident.make_room(700, 800, Some("small")), None);
+        assert_eq!(resident.footprint_mb(), 1200);
+        assert_eq!(
+            resident.make_room(600, 800, Some("small")),
+            Some(vec!["large".to_string()])
+        );
+        assert!(resident.contains("small"));
+        assert_eq!(
+            resident.make_room(800, 800, None),
+            Some(vec!["small".to_string()])
+        );
+        assert_eq!(resident.footprint_mb(), 0);
+    }
+}
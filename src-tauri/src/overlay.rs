@@ -1,10 +1,16 @@
+use crate::managers::audio::AudioRecordingManager;
+use crate::managers::queue::TranscriptionQueue;
 use crate::settings;
 use crate::settings::OverlayPosition;
 use log::debug;
+use std::sync::Arc;
+use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager, WebviewWindowBuilder};
 
 const OVERLAY_WIDTH: f64 = 172.0;
 const OVERLAY_HEIGHT: f64 = 36.0;
+/// How long the "nothing heard" state stays up.
+const NOTHING_HEARD_DURATION: Duration = Duration::from_millis(1200);
 
 #[cfg(target_os = "macos")]
 const OVERLAY_TOP_OFFSET: f64 = 46.0;
@@ -107,6 +113,38 @@ pub fn show_transcribing_overlay(app_handle: &AppHandle) {
     }
 }
 
+/// Briefly tells the user that a recording was discarded because no speech
+/// was heard, then falls back to whatever the app is doing by then.
+pub fn show_nothing_heard_overlay(app_handle: &AppHandle) {
+    // Check if overlay should be shown based on position setting
+    let settings = settings::get_settings(app_handle);
+    if settings.overlay_position == OverlayPosition::None {
+        return;
+    }
+
+    if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
+        let _ = overlay_window.show();
+        let _ = overlay_window.emit("show-overlay", "nothing_heard");
+    }
+
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(NOTHING_HEARD_DURATION);
+        // A new recording shows its own overlay
+        if app_handle
+            .state::<Arc<AudioRecordingManager>>()
+            .is_recording()
+        {
+            return;
+        }
+        if app_handle.state::<Arc<TranscriptionQueue>>().depth() > 0 {
+            show_transcribing_overlay(&app_handle);
+        } else {
+            hide_recording_overlay(&app_handle);
+        }
+    });
+}
+
 /// Updates the overlay window position based on current settings
 pub fn update_overlay_position(app_handle: &AppHandle) {
     if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
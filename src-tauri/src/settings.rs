@@ -1,5 +1,7 @@
+use crate::audio_toolkit::ReplacementRule;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 use tauri::{App, AppHandle};
 use tauri_plugin_store::StoreExt;
 
@@ -20,40 +22,177 @@ pub enum OverlayPosition {
     Bottom,
 }
 
+/// How long the model may go unused before it is unloaded.
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
-#[serde(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case", from = "StoredModelUnloadTimeout")]
 pub enum ModelUnloadTimeout {
     #[default]
     Never,
-    Immediately,
     Min2,
     Min5,
     Min10,
     Min15,
     Hour1,
-    Sec5, // Debug mode only
+    /// Any other duration, in seconds.
+    Seconds(u64),
 }
 
 impl ModelUnloadTimeout {
-    pub fn to_minutes(self) -> Option<u64> {
+    pub fn to_seconds(self) -> Option<u64> {
         match self {
             ModelUnloadTimeout::Never => None,
-            ModelUnloadTimeout::Immediately => Some(0), // Special case for immediate unloading
-            ModelUnloadTimeout::Min2 => Some(2),
-            ModelUnloadTimeout::Min5 => Some(5),
-            ModelUnloadTimeout::Min10 => Some(10),
-            ModelUnloadTimeout::Min15 => Some(15),
-            ModelUnloadTimeout::Hour1 => Some(60),
-            ModelUnloadTimeout::Sec5 => Some(0), // Special case for debug - handled separately
+            ModelUnloadTimeout::Min2 => Some(2 * 60),
+            ModelUnloadTimeout::Min5 => Some(5 * 60),
+            ModelUnloadTimeout::Min10 => Some(10 * 60),
+            ModelUnloadTimeout::Min15 => Some(15 * 60),
+            ModelUnloadTimeout::Hour1 => Some(60 * 60),
+            ModelUnloadTimeout::Seconds(seconds) => Some(seconds),
         }
     }
 
-    pub fn to_seconds(self) -> Option<u64> {
-        match self {
-            ModelUnloadTimeout::Never => None,
-            ModelUnloadTimeout::Immediately => Some(0), // Special case for immediate unloading
-            ModelUnloadTimeout::Sec5 => Some(5),
-            _ => self.to_minutes().map(|m| m * 60),
+    pub fn to_duration(self) -> Option<Duration> {
+        self.to_seconds().map(Duration::from_secs)
+    }
+}
+
+/// Every value `model_unload_timeout` has been stored as, including the
+/// removed `immediately` and `sec5` options.
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum StoredModelUnloadTimeout {
+    Never,
+    Immediately,
+    Min2,
+    Min5,
+    Min10,
+    Min15,
+    Hour1,
+    Sec5,
+    Seconds(u64),
+}
+
+impl From<StoredModelUnloadTimeout> for ModelUnloadTimeout {
+    fn from(stored: StoredModelUnloadTimeout) -> Self {
+        match stored {
+            StoredModelUnloadTimeout::Never => ModelUnloadTimeout::Never,
+            StoredModelUnloadTimeout::Min2 => ModelUnloadTimeout::Min2,
+            StoredModelUnloadTimeout::Min5 => ModelUnloadTimeout::Min5,
+            StoredModelUnloadTimeout::Min10 => ModelUnloadTimeout::Min10,
+            StoredModelUnloadTimeout::Min15 => ModelUnloadTimeout::Min15,
+            StoredModelUnloadTimeout::Hour1 => ModelUnloadTimeout::Hour1,
+            // Unloaded as soon as the last transcription finishes
+            StoredModelUnloadTimeout::Immediately => ModelUnloadTimeout::Seconds(0),
+            StoredModelUnloadTimeout::Sec5 => ModelUnloadTimeout::Seconds(5),
+            StoredModelUnloadTimeout::Seconds(seconds) => ModelUnloadTimeout::Seconds(seconds),
+        }
+    }
+}
+
+/// Thresholds a recording has to pass to be transcribed. Recordings failing
+/// any of them, such as accidental taps of the shortcut, are discarded before
+/// the model is involved. Setting a threshold to 0 disables it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SpeechGateSettings {
+    /// Minimum amount of speech detected by the VAD, in milliseconds.
+    #[serde(default = "default_min_speech_ms")]
+    pub min_speech_ms: u64,
+    /// Minimum RMS level of the speech, from 0.0 to 1.0.
+    #[serde(default = "default_min_speech_rms")]
+    pub min_rms: f32,
+    /// Minimum share of the recording the VAD classified as speech, from 0.0
+    /// to 1.0.
+    #[serde(default = "default_min_speech_ratio")]
+    pub min_speech_ratio: f32,
+}
+
+impl Default for SpeechGateSettings {
+    fn default() -> Self {
+        Self {
+            min_speech_ms: default_min_speech_ms(),
+            min_rms: default_min_speech_rms(),
+            min_speech_ratio: default_min_speech_ratio(),
+        }
+    }
+}
+
+/// Spoken punctuation and formatting commands ("comma", "new paragraph"),
+/// converted after word correction.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SpokenPunctuationSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Extra phrases keyed by language code ("en", "de"), each mapping the
+    /// spoken phrase to its output. They take precedence over the built-in
+    /// commands of that language.
+    #[serde(default)]
+    pub custom_phrases: HashMap<String, HashMap<String, String>>,
+}
+
+/// How an engine picks tokens while decoding.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "strategy")]
+pub enum DecodingStrategy {
+    /// Fastest; takes the most likely token at every step.
+    Greedy,
+    /// Keeps the `beam_size` best candidates; slower but more accurate.
+    BeamSearch { beam_size: u32 },
+}
+
+/// Weights Parakeet models are loaded with.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelPrecision {
+    /// Quantized weights; smaller and faster on CPU.
+    #[default]
+    Int8,
+    /// Full-precision weights; the model folder must contain them.
+    Full,
+}
+
+/// Inference tuning for a single model. `None` keeps the engine default, and
+/// only what the model's engine supports may be set.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ModelInferenceSettings {
+    #[serde(default)]
+    pub threads: Option<u32>,
+    #[serde(default)]
+    pub decoding: Option<DecodingStrategy>,
+    /// Retry at higher temperatures when decoding fails quality checks.
+    #[serde(default)]
+    pub temperature_fallback: Option<bool>,
+    /// Segments more likely than this to be silence are skipped, from 0.0 to
+    /// 1.0.
+    #[serde(default)]
+    pub no_speech_threshold: Option<f32>,
+}
+
+/// Connection to an OpenAI-compatible transcription server used by the
+/// remote engine.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RemoteEngineSettings {
+    /// Full endpoint URL, e.g. `http://localhost:8000/v1/audio/transcriptions`.
+    /// The remote model is only offered once this is set.
+    #[serde(default)]
+    pub endpoint: String,
+    /// Sent as a bearer token when set.
+    #[serde(default)]
+    pub api_key: Option<String>,
+    #[serde(default = "default_remote_model")]
+    pub model: String,
+    #[serde(default = "default_remote_timeout_seconds")]
+    pub timeout_seconds: u64,
+    #[serde(default = "default_remote_max_retries")]
+    pub max_retries: u32,
+}
+
+impl Default for RemoteEngineSettings {
+    fn default() -> Self {
+        Self {
+            endpoint: String::new(),
+            api_key: None,
+            model: default_remote_model(),
+            timeout_seconds: default_remote_timeout_seconds(),
+            max_retries: default_remote_max_retries(),
         }
     }
 }
@@ -86,8 +225,45 @@ pub struct AppSettings {
     pub custom_words: Vec<String>,
     #[serde(default)]
     pub model_unload_timeout: ModelUnloadTimeout,
+    /// Approximate memory, in MB, that loaded models may use together. The
+    /// active model always stays loaded, so 0 keeps only that one.
+    #[serde(default = "default_model_memory_budget_mb")]
+    pub model_memory_budget_mb: u64,
     #[serde(default = "default_word_correction_threshold")]
     pub word_correction_threshold: f64,
+    /// Words the custom word correction never replaces.
+    #[serde(default)]
+    pub protected_words: Vec<String>,
+    /// Also protect the most frequent words of the spoken language.
+    #[serde(default = "default_protect_frequent_words")]
+    pub protect_frequent_words: bool,
+    #[serde(default = "default_max_chunk_seconds")]
+    pub max_chunk_seconds: u32,
+    #[serde(default)]
+    pub prompt_context: String,
+    /// Languages auto-detection may choose from. Empty means any language.
+    #[serde(default)]
+    pub language_candidates: Vec<String>,
+    #[serde(default)]
+    pub context_carry_over: bool,
+    #[serde(default = "default_context_carry_over_idle_seconds")]
+    pub context_carry_over_idle_seconds: u64,
+    #[serde(default)]
+    pub remote_engine: RemoteEngineSettings,
+    #[serde(default)]
+    pub speech_gate: SpeechGateSettings,
+    /// Explicit replacements, applied in order after fuzzy custom word
+    /// matching.
+    #[serde(default)]
+    pub replacement_rules: Vec<ReplacementRule>,
+    #[serde(default)]
+    pub spoken_punctuation: SpokenPunctuationSettings,
+    /// Inference tuning keyed by model id. Models without an entry use the
+    /// engine defaults.
+    #[serde(default)]
+    pub model_inference: HashMap<String, ModelInferenceSettings>,
+    #[serde(default)]
+    pub parakeet_precision: ModelPrecision,
 }
 
 fn default_model() -> String {
@@ -118,10 +294,55 @@ fn default_debug_mode() -> bool {
     false
 }
 
+fn default_protect_frequent_words() -> bool {
+    false
+}
+
 fn default_word_correction_threshold() -> f64 {
     0.18
 }
 
+fn default_max_chunk_seconds() -> u32 {
+    28
+}
+
+fn default_model_memory_budget_mb() -> u64 {
+    0
+}
+
+fn default_context_carry_over_idle_seconds() -> u64 {
+    120
+}
+
+fn default_min_speech_ms() -> u64 {
+    300
+}
+
+fn default_min_speech_rms() -> f32 {
+    0.003
+}
+
+fn default_min_speech_ratio() -> f32 {
+    0.01
+}
+
+fn default_remote_model() -> String {
+    "whisper-1".to_string()
+}
+
+fn default_remote_timeout_seconds() -> u64 {
+    60
+}
+
+fn default_remote_max_retries() -> u32 {
+    2
+}
+
+/// Upper bounds for the remote engine settings, so a typo can't stall a
+/// dictation for hours.
+pub const MAX_REMOTE_TIMEOUT_SECONDS: u64 = 600;
+pub const MAX_REMOTE_RETRIES: u32 = 10;
+
 pub const SETTINGS_STORE_PATH: &str = "settings_store.json";
 
 pub fn get_default_settings() -> AppSettings {
@@ -161,7 +382,21 @@ pub fn get_default_settings() -> AppSettings {
         debug_mode: false,
         custom_words: Vec::new(),
         model_unload_timeout: ModelUnloadTimeout::Never,
+        model_memory_budget_mb: default_model_memory_budget_mb(),
         word_correction_threshold: default_word_correction_threshold(),
+        protected_words: Vec::new(),
+        protect_frequent_words: default_protect_frequent_words(),
+        max_chunk_seconds: default_max_chunk_seconds(),
+        prompt_context: String::new(),
+        language_candidates: Vec::new(),
+        context_carry_over: false,
+        context_carry_over_idle_seconds: default_context_carry_over_idle_seconds(),
+        remote_engine: RemoteEngineSettings::default(),
+        speech_gate: SpeechGateSettings::default(),
+        replacement_rules: Vec::new(),
+        spoken_punctuation: SpokenPunctuationSettings::default(),
+        model_inference: HashMap::new(),
+        parakeet_precision: ModelPrecision::default(),
     }
 }
 
@@ -237,3 +472,22 @@ pub fn get_stored_binding(app: &AppHandle, id: &str) -> ShortcutBinding {
 
     binding
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn model_unload_timeout_reads_legacy_values() {
+        let parse = |json: &str| serde_json::from_str::<ModelUnloadTimeout>(json).unwrap();
+
+        assert_eq!(parse(r#""min5""#), ModelUnloadTimeout::Min5);
+        assert_eq!(parse(r#""immediately""#), ModelUnloadTimeout::Seconds(0));
+        assert_eq!(parse(r#""sec5""#), ModelUnloadTimeout::Seconds(5));
+        assert_eq!(parse(r#"{"seconds":90}"#), ModelUnloadTimeout::Seconds(90));
+        assert_eq!(
+            serde_json::to_string(&ModelUnloadTimeout::Seconds(90)).unwrap(),
+            r#"{"seconds":90}"#
+        );
+    }
+}
@@ -1,11 +1,20 @@
 use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Arc;
 use tauri::{App, AppHandle, Emitter, Manager};
 use tauri_plugin_global_shortcut::GlobalShortcutExt;
 use tauri_plugin_global_shortcut::{Shortcut, ShortcutState};
 
 use crate::actions::ACTION_MAP;
+use crate::audio_toolkit::replacement::{rules_from_csv, rules_to_csv};
+use crate::audio_toolkit::ReplacementRule;
+use crate::managers::model::{ModelManager, REMOTE_MODEL_ID};
+use crate::managers::transcription::TranscriptionManager;
 use crate::settings::ShortcutBinding;
-use crate::settings::{self, get_settings, OverlayPosition};
+use crate::settings::{
+    self, get_settings, OverlayPosition, RemoteEngineSettings, SpeechGateSettings,
+    SpokenPunctuationSettings,
+};
 use crate::ManagedToggleState;
 
 pub fn init_shortcuts(app: &App) {
@@ -199,6 +208,189 @@ pub fn update_custom_words(app: AppHandle, words: Vec<String>) -> Result<(), Str
     Ok(())
 }
 
+#[tauri::command]
+pub fn update_protected_words(app: AppHandle, words: Vec<String>) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.protected_words = words;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_protect_frequent_words_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.protect_frequent_words = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_replacement_rules_setting(
+    app: AppHandle,
+    rules: Vec<ReplacementRule>,
+) -> Result<(), String> {
+    for rule in &rules {
+        rule.compile().map_err(|e| format!("{:#}", e))?;
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.replacement_rules = rules;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Replaces the replacement rules with the ones in a CSV file and returns
+/// them.
+#[tauri::command]
+pub fn import_replacement_rules(
+    app: AppHandle,
+    path: String,
+) -> Result<Vec<ReplacementRule>, String> {
+    let csv =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let rules = rules_from_csv(&csv).map_err(|e| format!("{:#}", e))?;
+
+    let mut settings = settings::get_settings(&app);
+    settings.replacement_rules = rules.clone();
+    settings::write_settings(&app, settings);
+    Ok(rules)
+}
+
+#[tauri::command]
+pub fn export_replacement_rules(app: AppHandle, path: String) -> Result<(), String> {
+    let settings = settings::get_settings(&app);
+    let csv = rules_to_csv(&settings.replacement_rules).map_err(|e| format!("{:#}", e))?;
+    std::fs::write(&path, csv).map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+#[tauri::command]
+pub fn change_language_candidates_setting(
+    app: AppHandle,
+    languages: Vec<String>,
+) -> Result<(), String> {
+    let mut languages: Vec<String> = languages
+        .into_iter()
+        .map(|l| l.trim().to_lowercase())
+        .filter(|l| !l.is_empty())
+        .collect();
+    if languages.iter().any(|l| l == "auto") {
+        return Err("\"auto\" is not a language".into());
+    }
+    // Keeps the first occurrence of each language, in the order given
+    let mut seen = HashSet::new();
+    languages.retain(|l| seen.insert(l.clone()));
+    let mut settings = settings::get_settings(&app);
+    settings.language_candidates = languages;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_prompt_context_setting(app: AppHandle, context: String) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.prompt_context = context;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_context_carry_over_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.context_carry_over = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_context_carry_over_idle_setting(app: AppHandle, seconds: u64) -> Result<(), String> {
+    if seconds == 0 {
+        return Err("Idle gap must be at least one second".into());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.context_carry_over_idle_seconds = seconds;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_remote_engine_settings(
+    app: AppHandle,
+    remote_engine: RemoteEngineSettings,
+) -> Result<(), String> {
+    let endpoint = remote_engine.endpoint.trim().to_string();
+    if !endpoint.is_empty() && !endpoint.starts_with("http://") && !endpoint.starts_with("https://")
+    {
+        return Err("Remote endpoint must start with http:// or https://".into());
+    }
+    if remote_engine.timeout_seconds == 0 {
+        return Err("Timeout must be at least one second".into());
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.remote_engine = RemoteEngineSettings {
+        endpoint,
+        timeout_seconds: remote_engine
+            .timeout_seconds
+            .min(settings::MAX_REMOTE_TIMEOUT_SECONDS),
+        max_retries: remote_engine.max_retries.min(settings::MAX_REMOTE_RETRIES),
+        ..remote_engine
+    };
+    settings::write_settings(&app, settings);
+
+    // The remote engine reads its settings when it is loaded
+    let tm = app.state::<Arc<TranscriptionManager>>();
+    tm.evict_model(REMOTE_MODEL_ID);
+    app.state::<Arc<ModelManager>>()
+        .update_download_status()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn change_speech_gate_settings(
+    app: AppHandle,
+    speech_gate: SpeechGateSettings,
+) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&speech_gate.min_rms) {
+        return Err("Minimum level must be between 0 and 1".into());
+    }
+    if !(0.0..=1.0).contains(&speech_gate.min_speech_ratio) {
+        return Err("Minimum speech ratio must be between 0 and 1".into());
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.speech_gate = speech_gate;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_spoken_punctuation_settings(
+    app: AppHandle,
+    spoken_punctuation: SpokenPunctuationSettings,
+) -> Result<(), String> {
+    for (language, phrases) in &spoken_punctuation.custom_phrases {
+        if phrases.keys().any(|phrase| phrase.trim().is_empty()) {
+            return Err(format!("Empty spoken phrase for language {}", language));
+        }
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.spoken_punctuation = spoken_punctuation;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn change_model_memory_budget_setting(app: AppHandle, budget_mb: u64) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.model_memory_budget_mb = budget_mb;
+    settings::write_settings(&app, settings);
+
+    app.state::<Arc<TranscriptionManager>>()
+        .enforce_memory_budget();
+    Ok(())
+}
+
 #[tauri::command]
 pub fn change_word_correction_threshold_setting(
     app: AppHandle,
@@ -210,6 +402,17 @@ pub fn change_word_correction_threshold_setting(
     Ok(())
 }
 
+#[tauri::command]
+pub fn change_max_chunk_seconds_setting(app: AppHandle, seconds: u32) -> Result<(), String> {
+    if !(5..=30).contains(&seconds) {
+        return Err("Chunk length must be between 5 and 30 seconds".into());
+    }
+    let mut settings = settings::get_settings(&app);
+    settings.max_chunk_seconds = seconds;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 /// Determine whether a shortcut string contains at least one non-modifier key.
 /// We allow single non-modifier keys (e.g. "f5" or "space") but disallow
 /// modifier-only combos (e.g. "ctrl" or "ctrl+shift").
@@ -1,5 +1,7 @@
 use crate::actions::ACTION_MAP;
 use crate::managers::audio::AudioRecordingManager;
+use crate::managers::queue::TranscriptionQueue;
+use crate::managers::transcription::TranscriptionManager;
 use crate::ManagedToggleState;
 use log::{error, info, warn};
 use std::sync::Arc;
@@ -49,6 +51,11 @@ pub fn cancel_current_operation(app: &AppHandle) {
     let audio_manager = app.state::<Arc<AudioRecordingManager>>();
     audio_manager.cancel_recording();
 
+    // Stop transcribing every dictation so nothing gets pasted or saved
+    let transcription_manager = app.state::<Arc<TranscriptionManager>>();
+    transcription_manager.cancel_dictation();
+    app.state::<Arc<TranscriptionQueue>>().cancel_all();
+
     // Update tray icon and menu to idle state
     change_tray_icon(app, crate::tray::TrayIconState::Idle);
 
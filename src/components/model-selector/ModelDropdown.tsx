@@ -1,5 +1,5 @@
 import React from "react";
-import { ModelInfo } from "../../lib/types";
+import { ModelBenchmark, ModelInfo } from "../../lib/types";
 import { ProgressBar, ProgressData } from "../shared";
 
 interface DownloadProgress {
@@ -13,6 +13,10 @@ interface ModelDropdownProps {
   models: ModelInfo[];
   currentModelId: string;
   downloadProgress: Map<string, DownloadProgress>;
+  benchmarks: ModelBenchmark[];
+  recommendedModelId: string;
+  isBenchmarking: boolean;
+  onBenchmark: () => void;
   onModelSelect: (modelId: string) => void;
   onModelDownload: (modelId: string) => void;
   onModelDelete: (modelId: string) => Promise<void>;
@@ -23,15 +27,29 @@ const ModelDropdown: React.FC<ModelDropdownProps> = ({
   models,
   currentModelId,
   downloadProgress,
+  benchmarks,
+  recommendedModelId,
+  isBenchmarking,
+  onBenchmark,
   onModelSelect,
   onModelDownload,
   onModelDelete,
   onError,
 }) => {
   const availableModels = models.filter((m) => m.is_downloaded);
-  const downloadableModels = models.filter((m) => !m.is_downloaded);
+  const downloadableModels = models.filter(
+    (m) => !m.is_downloaded && m.engine_type !== "Remote",
+  );
   const isFirstRun = availableModels.length === 0 && models.length > 0;
 
+  // Expected wait for a typical ten second dictation on this machine
+  const expectedLatency = (modelId: string) => {
+    const benchmark = benchmarks.find((b) => b.model_id === modelId);
+    if (!benchmark) return null;
+    const seconds = benchmark.real_time_factor * 10;
+    return `~${seconds.toFixed(1)}s per 10s of speech`;
+  };
+
   const handleDeleteClick = async (e: React.MouseEvent, modelId: string) => {
     e.preventDefault();
     e.stopPropagation();
@@ -102,6 +120,11 @@ const ModelDropdown: React.FC<ModelDropdownProps> = ({
                   <div className="text-xs text-text/40 italic pr-4">
                     {model.description}
                   </div>
+                  {expectedLatency(model.id) && (
+                    <div className="text-xs text-text/60 tabular-nums">
+                      {expectedLatency(model.id)}
+                    </div>
+                  )}
                 </div>
                 <div className="flex items-center gap-2">
                   {currentModelId === model.id && (
@@ -130,6 +153,15 @@ const ModelDropdown: React.FC<ModelDropdownProps> = ({
               </div>
             </div>
           ))}
+          <button
+            onClick={onBenchmark}
+            disabled={isBenchmarking}
+            className="w-full px-3 py-1.5 text-left text-xs text-text/60 hover:text-logo-primary transition-colors cursor-pointer disabled:cursor-wait disabled:hover:text-text/60"
+          >
+            {isBenchmarking
+              ? "Measuring speed..."
+              : "Measure speed on this machine"}
+          </button>
         </div>
       )}
 
@@ -169,7 +201,7 @@ const ModelDropdown: React.FC<ModelDropdownProps> = ({
                   <div>
                     <div className="text-sm">
                       {model.name}
-                      {model.id === "small" && isFirstRun && (
+                      {model.id === recommendedModelId && isFirstRun && (
                         <span className="ml-2 text-xs bg-logo-primary/20 text-logo-primary px-1.5 py-0.5 rounded">
                           Recommended
                         </span>
@@ -1,7 +1,7 @@
 import React, { useState, useRef, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { ModelInfo } from "../../lib/types";
+import { BenchmarkResults, ModelBenchmark, ModelInfo } from "../../lib/types";
 import ModelStatusButton from "./ModelStatusButton";
 import ModelDropdown from "./ModelDropdown";
 import DownloadProgressDisplay from "./DownloadProgressDisplay";
@@ -49,12 +49,16 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
   const [extractingModels, setExtractingModels] = useState<Set<string>>(
     new Set(),
   );
+  const [benchmarks, setBenchmarks] = useState<ModelBenchmark[]>([]);
+  const [recommendedModelId, setRecommendedModelId] = useState("");
+  const [isBenchmarking, setIsBenchmarking] = useState(false);
 
   const dropdownRef = useRef<HTMLDivElement>(null);
 
   useEffect(() => {
     loadModels();
     loadCurrentModel();
+    loadBenchmarks();
 
     // Listen for model state changes
     const modelStateUnlisten = listen<ModelStateEvent>(
@@ -235,6 +239,35 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
     }
   };
 
+  const loadBenchmarks = async () => {
+    try {
+      const results = await invoke<BenchmarkResults | null>(
+        "get_benchmark_results",
+      );
+      setBenchmarks(results?.models ?? []);
+      setRecommendedModelId(
+        await invoke<string>("get_recommended_first_model"),
+      );
+    } catch (err) {
+      console.error("Failed to load benchmark results:", err);
+    }
+  };
+
+  const handleBenchmark = async () => {
+    setIsBenchmarking(true);
+    try {
+      const results = await invoke<BenchmarkResults>("run_model_benchmark");
+      setBenchmarks(results.models);
+      setRecommendedModelId(
+        await invoke<string>("get_recommended_first_model"),
+      );
+    } catch (err) {
+      onError?.(`Failed to measure model speed: ${err}`);
+    } finally {
+      setIsBenchmarking(false);
+    }
+  };
+
   const loadCurrentModel = async () => {
     try {
       const current = await invoke<string>("get_current_model");
@@ -356,6 +389,10 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
             models={models}
             currentModelId={currentModelId}
             downloadProgress={modelDownloadProgress}
+            benchmarks={benchmarks}
+            recommendedModelId={recommendedModelId}
+            isBenchmarking={isBenchmarking}
+            onBenchmark={handleBenchmark}
             onModelSelect={handleModelSelect}
             onModelDownload={handleModelDownload}
             onModelDelete={handleModelDelete}
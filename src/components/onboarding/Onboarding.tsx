@@ -19,7 +19,9 @@ const Onboarding: React.FC<OnboardingProps> = ({ onModelSelected }) => {
     try {
       const models: ModelInfo[] = await invoke("get_available_models");
       // Only show downloadable models for onboarding
-      setAvailableModels(models.filter((m) => !m.is_downloaded));
+      setAvailableModels(
+        models.filter((m) => !m.is_downloaded && m.engine_type !== "Remote"),
+      );
     } catch (err) {
       console.error("Failed to load models:", err);
       setError("Failed to load available models");
@@ -12,6 +12,9 @@ interface HistoryEntry {
   saved: boolean;
   title: string;
   transcription_text: string;
+  language: string | null;
+  filtered: string | null;
+  source_file: string | null;
 }
 
 export const HistorySettings: React.FC = () => {
@@ -200,7 +203,30 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
   return (
     <div className="px-4 py-2 pb-5 flex flex-col gap-3">
       <div className="flex justify-between items-center">
-        <p className="text-sm font-medium">{entry.title}</p>
+        <p className="text-sm font-medium">
+          {entry.title}
+          {entry.language && (
+            <span className="ml-2 text-xs text-text/50 uppercase">
+              {entry.language}
+            </span>
+          )}
+          {entry.filtered && (
+            <span
+              className="ml-2 text-xs text-text/50"
+              title={`Hallucination filter: ${entry.filtered.replace("_", " ")}`}
+            >
+              filtered
+            </span>
+          )}
+          {entry.source_file && (
+            <span
+              className="ml-2 text-xs text-text/50"
+              title="Transcribed from file"
+            >
+              {entry.source_file}
+            </span>
+          )}
+        </p>
         <div className="flex items-center gap-1">
           <button
             onClick={handleCopyText}
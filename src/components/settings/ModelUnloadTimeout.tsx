@@ -1,8 +1,13 @@
-import React, { useMemo } from "react";
+import React, { useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { useSettings } from "../../hooks/useSettings";
-import { ModelUnloadTimeout } from "../../lib/types";
+import {
+  ModelUnloadTimeout,
+  ModelUnloadTimeoutPreset,
+  ModelUnloadTimeoutPresetSchema,
+} from "../../lib/types";
 import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
 import { SettingContainer } from "../ui/SettingContainer";
 
 interface ModelUnloadTimeoutProps {
@@ -10,43 +15,71 @@ interface ModelUnloadTimeoutProps {
   grouped?: boolean;
 }
 
+const CUSTOM = "custom";
+const DEFAULT_CUSTOM_SECONDS = 300;
+
 const timeoutOptions = [
-  { value: "never" as ModelUnloadTimeout, label: "Never" },
-  { value: "immediately" as ModelUnloadTimeout, label: "Immediately" },
-  { value: "min2" as ModelUnloadTimeout, label: "After 2 minutes" },
-  { value: "min5" as ModelUnloadTimeout, label: "After 5 minutes" },
-  { value: "min10" as ModelUnloadTimeout, label: "After 10 minutes" },
-  { value: "min15" as ModelUnloadTimeout, label: "After 15 minutes" },
-  { value: "hour1" as ModelUnloadTimeout, label: "After 1 hour" },
+  { value: "never", label: "Never" },
+  { value: "min2", label: "After 2 minutes" },
+  { value: "min5", label: "After 5 minutes" },
+  { value: "min10", label: "After 10 minutes" },
+  { value: "min15", label: "After 15 minutes" },
+  { value: "hour1", label: "After 1 hour" },
+  { value: CUSTOM, label: "Custom" },
 ];
 
-const debugTimeoutOptions = [
-  ...timeoutOptions,
-  { value: "sec5" as ModelUnloadTimeout, label: "After 5 seconds (Debug)" },
-];
+// Older settings may still hold a removed preset; the backend reads those
+// as a 5 second timeout.
+const normalizeTimeout = (stored: unknown): ModelUnloadTimeout => {
+  if (typeof stored === "object" && stored !== null && "seconds" in stored) {
+    return stored as ModelUnloadTimeout;
+  }
+  const preset = ModelUnloadTimeoutPresetSchema.safeParse(stored ?? "never");
+  return preset.success ? preset.data : { seconds: 5 };
+};
 
 export const ModelUnloadTimeoutSetting: React.FC<ModelUnloadTimeoutProps> = ({
   descriptionMode = "inline",
   grouped = false,
 }) => {
-  const { settings, getSetting, updateSetting } = useSettings();
+  const { getSetting, updateSetting } = useSettings();
 
-  const handleChange = async (event: React.ChangeEvent<HTMLSelectElement>) => {
-    const newTimeout = event.target.value as ModelUnloadTimeout;
+  const currentValue = normalizeTimeout(getSetting("model_unload_timeout"));
+  const [customSeconds, setCustomSeconds] = useState(
+    String(
+      typeof currentValue === "object"
+        ? currentValue.seconds
+        : DEFAULT_CUSTOM_SECONDS,
+    ),
+  );
 
+  const saveTimeout = async (timeout: ModelUnloadTimeout) => {
     try {
-      await invoke("set_model_unload_timeout", { timeout: newTimeout });
-      updateSetting("model_unload_timeout", newTimeout);
+      await invoke("set_model_unload_timeout", { timeout });
+      updateSetting("model_unload_timeout", timeout);
     } catch (error) {
       console.error("Failed to update model unload timeout:", error);
     }
   };
 
-  const currentValue = getSetting("model_unload_timeout") ?? "never";
+  const saveCustomSeconds = () => {
+    const seconds = Number.parseInt(customSeconds, 10);
+    if (Number.isNaN(seconds) || seconds < 1) {
+      setCustomSeconds(String(DEFAULT_CUSTOM_SECONDS));
+      return;
+    }
+    saveTimeout({ seconds });
+  };
+
+  const handleSelect = (value: string) => {
+    if (value === CUSTOM) {
+      saveCustomSeconds();
+    } else {
+      saveTimeout(value as ModelUnloadTimeoutPreset);
+    }
+  };
 
-  const options = useMemo(() => {
-    return settings?.debug_mode === true ? debugTimeoutOptions : timeoutOptions;
-  }, [settings]);
+  const isCustom = typeof currentValue === "object";
 
   return (
     <SettingContainer
@@ -55,16 +88,28 @@ export const ModelUnloadTimeoutSetting: React.FC<ModelUnloadTimeoutProps> = ({
       descriptionMode={descriptionMode}
       grouped={grouped}
     >
-      <Dropdown
-        options={options}
-        selectedValue={currentValue}
-        onSelect={(value) =>
-          handleChange({
-            target: { value },
-          } as React.ChangeEvent<HTMLSelectElement>)
-        }
-        disabled={false}
-      />
+      <div className="flex items-center gap-2">
+        {isCustom && (
+          <Input
+            type="number"
+            min={1}
+            className="w-24"
+            value={customSeconds}
+            onChange={(e) => setCustomSeconds(e.target.value)}
+            onBlur={saveCustomSeconds}
+            onKeyDown={(e) => e.key === "Enter" && saveCustomSeconds()}
+            variant="compact"
+            aria-label="Seconds"
+          />
+        )}
+        {isCustom && <span className="text-sm">seconds</span>}
+        <Dropdown
+          options={timeoutOptions}
+          selectedValue={isCustom ? CUSTOM : currentValue}
+          onSelect={handleSelect}
+          disabled={false}
+        />
+      </div>
     </SettingContainer>
   );
 };
@@ -0,0 +1,29 @@
+import React from "react";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface ProtectFrequentWordsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const ProtectFrequentWords: React.FC<ProtectFrequentWordsProps> = React.memo(({
+  descriptionMode = "tooltip",
+  grouped = false,
+}) => {
+  const { getSetting, updateSetting, isUpdating } = useSettings();
+
+  const protectFrequentWords = getSetting("protect_frequent_words") || false;
+
+  return (
+    <ToggleSwitch
+      checked={protectFrequentWords}
+      onChange={(enabled) => updateSetting("protect_frequent_words", enabled)}
+      isUpdating={isUpdating("protect_frequent_words")}
+      label="Protect Common Words"
+      description="Never replace the most common words of the spoken language with custom words."
+      descriptionMode={descriptionMode}
+      grouped={grouped}
+    />
+  );
+});
@@ -22,15 +22,21 @@ export const AudioDeviceSchema = z.object({
 export const OverlayPositionSchema = z.enum(["none", "top", "bottom"]);
 export type OverlayPosition = z.infer<typeof OverlayPositionSchema>;
 
-export const ModelUnloadTimeoutSchema = z.enum([
+export const ModelUnloadTimeoutPresetSchema = z.enum([
   "never",
-  "immediately",
   "min2",
   "min5",
   "min10",
   "min15",
   "hour1",
-  "sec5",
+]);
+export type ModelUnloadTimeoutPreset = z.infer<
+  typeof ModelUnloadTimeoutPresetSchema
+>;
+
+export const ModelUnloadTimeoutSchema = z.union([
+  ModelUnloadTimeoutPresetSchema,
+  z.object({ seconds: z.number().int().nonnegative() }),
 ]);
 export type ModelUnloadTimeout = z.infer<typeof ModelUnloadTimeoutSchema>;
 
@@ -50,6 +56,7 @@ export const SettingsSchema = z.object({
   custom_words: z.array(z.string()).optional().default([]),
   model_unload_timeout: ModelUnloadTimeoutSchema.optional().default("never"),
   word_correction_threshold: z.number().optional().default(0.18),
+  protect_frequent_words: z.boolean().optional().default(false),
 });
 
 export const BindingResponseSchema = z.object({
@@ -75,6 +82,28 @@ export const ModelInfoSchema = z.object({
   is_downloading: z.boolean(),
   partial_size: z.number(),
   is_directory: z.boolean(),
+  engine_type: z.enum(["Whisper", "Parakeet", "Remote"]),
 });
 
 export type ModelInfo = z.infer<typeof ModelInfoSchema>;
+
+export const ModelBenchmarkSchema = z.object({
+  model_id: z.string(),
+  load_ms: z.number(),
+  transcribe_ms: z.number(),
+  clip_seconds: z.number(),
+  real_time_factor: z.number(),
+});
+
+export type ModelBenchmark = z.infer<typeof ModelBenchmarkSchema>;
+
+export const BenchmarkResultsSchema = z.object({
+  hardware: z.object({
+    cpu_threads: z.number(),
+    total_memory_mb: z.number(),
+  }),
+  measured_at: z.number(),
+  models: z.array(ModelBenchmarkSchema),
+});
+
+export type BenchmarkResults = z.infer<typeof BenchmarkResultsSchema>;
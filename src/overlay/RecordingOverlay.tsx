@@ -8,12 +8,18 @@ import {
 } from "../components/icons";
 import "./RecordingOverlay.css";
 
-type OverlayState = "recording" | "transcribing";
+type OverlayState = "recording" | "transcribing" | "nothing_heard";
+
+interface QueueStatus {
+  depth: number;
+  active_job: number | null;
+}
 
 const RecordingOverlay: React.FC = () => {
   const [isVisible, setIsVisible] = useState(false);
   const [state, setState] = useState<OverlayState>("recording");
   const [levels, setLevels] = useState<number[]>(Array(16).fill(0));
+  const [queueDepth, setQueueDepth] = useState(0);
   const smoothedLevelsRef = useRef<number[]>(Array(16).fill(0));
 
   useEffect(() => {
@@ -44,11 +50,20 @@ const RecordingOverlay: React.FC = () => {
         setLevels(smoothed.slice(0, 9));
       });
 
+      // Listen for transcription queue updates
+      const unlistenQueue = await listen<QueueStatus>(
+        "transcription-queue",
+        (event) => {
+          setQueueDepth(event.payload.depth);
+        },
+      );
+
       // Cleanup function
       return () => {
         unlistenShow();
         unlistenHide();
         unlistenLevel();
+        unlistenQueue();
       };
     };
 
@@ -56,7 +71,7 @@ const RecordingOverlay: React.FC = () => {
   }, []);
 
   const getIcon = () => {
-    if (state === "recording") {
+    if (state === "recording" || state === "nothing_heard") {
       return <MicrophoneIcon />;
     } else {
       return <TranscriptionIcon />;
@@ -84,11 +99,26 @@ const RecordingOverlay: React.FC = () => {
           </div>
         )}
         {state === "transcribing" && (
-          <div className="transcribing-text">Transcribing...</div>
+          <div className="transcribing-text">
+            {queueDepth > 1
+              ? `Transcribing (${queueDepth})...`
+              : "Transcribing..."}
+          </div>
+        )}
+        {state === "nothing_heard" && (
+          <div className="nothing-heard-text">Nothing heard</div>
         )}
       </div>
 
       <div className="overlay-right">
+        {state === "recording" && queueDepth > 0 && (
+          <div
+            className="queue-badge"
+            title="Recordings waiting to be transcribed"
+          >
+            {queueDepth}
+          </div>
+        )}
         {state === "recording" && (
           <div
             className="cancel-button"
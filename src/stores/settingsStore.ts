@@ -197,6 +197,9 @@ export const useSettingsStore = create<SettingsStore>()(
           case "word_correction_threshold":
             await invoke("change_word_correction_threshold_setting", { threshold: value });
             break;
+          case "protect_frequent_words":
+            await invoke("change_protect_frequent_words_setting", { enabled: value });
+            break;
           case "bindings":
           case "selected_model":
             break;